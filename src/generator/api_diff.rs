@@ -0,0 +1,548 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::generator::inspection::{
+    FieldReport, HandlerReport, Inspection, ModelReport, ParamReport, VariantReport,
+};
+use crate::parser::handler_params::ParamKind;
+
+/// How a change affects existing callers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Existing callers may fail, i.e. a removed route or a parameter made required.
+    Breaking,
+    /// Existing callers keep working, i.e. a removed parameter or a deprecation.
+    NonBreaking,
+    /// Something existing callers don't use yet, i.e. a new route or optional parameter.
+    Additive,
+}
+
+impl Severity {
+    /// Returns the label of the severity in the report, i.e. `non-breaking`.
+    fn label(&self) -> &'static str {
+        match self {
+            Severity::Breaking => "breaking",
+            Severity::NonBreaking => "non-breaking",
+            Severity::Additive => "additive",
+        }
+    }
+}
+
+/// A difference between two API snapshots.
+pub struct ApiChange {
+    /// How the change affects existing callers.
+    pub severity: Severity,
+    /// The route or model changed, i.e. `GET /thread/<_>` or `model Thread`.
+    pub subject: String,
+    /// What changed, i.e. "parameter `page` is now required".
+    pub description: String,
+}
+
+/// Which way the values of a model travel, see `model_directions`.
+#[derive(Default, Clone, Copy)]
+struct Direction {
+    /// Sent by callers, in a request body, path segment or query value.
+    input: bool,
+    /// Received by callers, in a response.
+    output: bool,
+}
+
+/// Returns the identifiers in a TypeScript type, i.e. `Paginated`, `Thread` for
+/// `Paginated<Thread[]>`.
+fn type_names(ts_type: &str) -> impl Iterator<Item = &str> {
+    ts_type
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|name| !name.is_empty())
+}
+
+/// Finds which way the values of each model travel, through the handlers referring to them
+/// directly or through the fields and variants of other models.
+///
+/// Models no handler refers to are treated as travelling both ways, as their uses are unknown.
+fn model_directions(inspection: &Inspection) -> BTreeMap<&str, Direction> {
+    let models: BTreeMap<&str, &ModelReport> = inspection
+        .models
+        .iter()
+        .map(|model| (model.name.as_str(), model))
+        .collect();
+
+    let mut directions: BTreeMap<&str, Direction> = BTreeMap::new();
+    let mut pending: Vec<(&str, bool)> = vec![];
+    for handler in inspection.handlers.iter().filter(|h| h.active) {
+        for ts_type in handler.params.iter().filter_map(|p| p.ts_type.as_deref()) {
+            pending.extend(type_names(ts_type).map(|name| (name, true)));
+        }
+        pending.extend(type_names(&handler.response.ts_type).map(|name| (name, false)));
+    }
+
+    while let Some((name, input)) = pending.pop() {
+        let Some(model) = models.get(name) else {
+            continue;
+        };
+        let direction = directions.entry(name).or_default();
+        let seen = match input {
+            true => std::mem::replace(&mut direction.input, true),
+            false => std::mem::replace(&mut direction.output, true),
+        };
+        if seen {
+            continue;
+        }
+
+        let fields = model
+            .fields
+            .iter()
+            .chain(model.variants.iter().flat_map(|variant| &variant.fields));
+        let types = fields
+            .map(|field| field.ts_type.as_str())
+            .chain(
+                model
+                    .variants
+                    .iter()
+                    .flat_map(|v| v.types.iter().map(String::as_str)),
+            )
+            .chain(model.rust_type.as_deref());
+        pending.extend(types.flat_map(type_names).map(|name| (name, input)));
+    }
+
+    for model in &inspection.models {
+        directions.entry(model.name.as_str()).or_insert(Direction {
+            input: true,
+            output: true,
+        });
+    }
+    directions
+}
+
+/// Identifies a route regardless of the names of its path parameters, i.e. `GET /thread/<_>`
+/// for `GET /thread/<id>?<page>`.
+fn route_key(handler: &HandlerReport) -> String {
+    let path = handler.uri.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path
+        .split('/')
+        .map(|segment| match segment {
+            s if s.starts_with('<') && s.ends_with("..>") => "<_..>",
+            s if s.starts_with('<') => "<_>",
+            s => s,
+        })
+        .collect();
+    format!("{} {}", handler.method, segments.join("/"))
+}
+
+/// Returns the parameters callers pass, keyed by name, or by position for path segments so
+/// renaming them in Rust isn't reported.
+fn signature_params(handler: &HandlerReport) -> BTreeMap<String, &ParamReport> {
+    let mut position = 0;
+    let mut params = BTreeMap::new();
+    for param in handler.params.iter().filter(|p| p.ts_type.is_some()) {
+        let key = match param.kind {
+            ParamKind::Path | ParamKind::Segments => {
+                position += 1;
+                format!("<{position}>")
+            }
+            _ => param.name.clone(),
+        };
+        params.insert(key, param);
+    }
+    params
+}
+
+/// Collects the changes to a route present in both snapshots.
+fn diff_handler(old: &HandlerReport, new: &HandlerReport, changes: &mut Vec<(Severity, String)>) {
+    let old_params = signature_params(old);
+    let new_params = signature_params(new);
+
+    for (key, param) in &old_params {
+        if !new_params.contains_key(key) {
+            changes.push((
+                Severity::NonBreaking,
+                format!("parameter `{}` removed", param.name),
+            ));
+        }
+    }
+    for (key, param) in &new_params {
+        let Some(before) = old_params.get(key) else {
+            changes.push(match param.optional {
+                true => (
+                    Severity::Additive,
+                    format!("optional parameter `{}` added", param.name),
+                ),
+                false => (
+                    Severity::Breaking,
+                    format!("required parameter `{}` added", param.name),
+                ),
+            });
+            continue;
+        };
+
+        if before.kind != param.kind {
+            changes.push((
+                Severity::Breaking,
+                format!(
+                    "parameter `{}` moved from {:?} to {:?}",
+                    param.name, before.kind, param.kind
+                ),
+            ));
+        }
+        if before.ts_type != param.ts_type {
+            changes.push((
+                Severity::Breaking,
+                format!(
+                    "parameter `{}` changed from `{}` to `{}`",
+                    param.name,
+                    before.ts_type.as_deref().unwrap_or_default(),
+                    param.ts_type.as_deref().unwrap_or_default()
+                ),
+            ));
+        }
+        match (before.optional, param.optional) {
+            (true, false) => changes.push((
+                Severity::Breaking,
+                format!("parameter `{}` is now required", param.name),
+            )),
+            (false, true) => changes.push((
+                Severity::NonBreaking,
+                format!("parameter `{}` is now optional", param.name),
+            )),
+            _ => {}
+        }
+    }
+
+    if old.response.kind != new.response.kind {
+        changes.push((
+            Severity::Breaking,
+            format!(
+                "response changed from {} to {}",
+                old.response.kind, new.response.kind
+            ),
+        ));
+    } else if old.response.ts_type != new.response.ts_type {
+        changes.push((
+            Severity::Breaking,
+            format!(
+                "response changed from `{}` to `{}`",
+                old.response.ts_type, new.response.ts_type
+            ),
+        ));
+    }
+    if old.response.error_type != new.response.error_type {
+        changes.push((
+            Severity::NonBreaking,
+            format!(
+                "error changed from `{}` to `{}`",
+                old.response.error_type.as_deref().unwrap_or("none"),
+                new.response.error_type.as_deref().unwrap_or("none")
+            ),
+        ));
+    }
+    if old.name != new.name {
+        changes.push((
+            Severity::NonBreaking,
+            format!("renamed from `{}` to `{}`", old.name, new.name),
+        ));
+    }
+    if old.deprecated.is_none() && new.deprecated.is_some() {
+        changes.push((Severity::NonBreaking, String::from("deprecated")));
+    }
+}
+
+/// Collects the changes to the fields of a struct or struct variant.
+///
+/// Whether a change breaks callers depends on which way the values travel: a new required field
+/// breaks callers sending the model, a removed field breaks callers reading it.
+fn diff_fields(
+    old: &[FieldReport],
+    new: &[FieldReport],
+    direction: Direction,
+    prefix: &str,
+    changes: &mut Vec<(Severity, String)>,
+) {
+    let severity = |breaks: bool| match breaks {
+        true => Severity::Breaking,
+        false => Severity::NonBreaking,
+    };
+    let old_fields: BTreeMap<&str, &FieldReport> =
+        old.iter().map(|f| (f.wire_name.as_str(), f)).collect();
+    let new_fields: BTreeMap<&str, &FieldReport> =
+        new.iter().map(|f| (f.wire_name.as_str(), f)).collect();
+
+    for name in old_fields.keys() {
+        if !new_fields.contains_key(name) {
+            changes.push((
+                severity(direction.output),
+                format!("{prefix}field `{name}` removed"),
+            ));
+        }
+    }
+    for (name, field) in &new_fields {
+        let Some(before) = old_fields.get(name) else {
+            changes.push(match (field.optional, direction.input) {
+                (false, true) => (
+                    Severity::Breaking,
+                    format!("{prefix}required field `{name}` added"),
+                ),
+                _ => (Severity::Additive, format!("{prefix}field `{name}` added")),
+            });
+            continue;
+        };
+
+        if before.ts_type != field.ts_type {
+            changes.push((
+                Severity::Breaking,
+                format!(
+                    "{prefix}field `{name}` changed from `{}` to `{}`",
+                    before.ts_type, field.ts_type
+                ),
+            ));
+        }
+        match (before.optional, field.optional) {
+            (true, false) => changes.push((
+                severity(direction.input),
+                format!("{prefix}field `{name}` is now required"),
+            )),
+            (false, true) => changes.push((
+                severity(direction.output),
+                format!("{prefix}field `{name}` is now optional"),
+            )),
+            _ => {}
+        }
+        if before.deprecated.is_none() && field.deprecated.is_some() {
+            changes.push((
+                Severity::NonBreaking,
+                format!("{prefix}field `{name}` deprecated"),
+            ));
+        }
+    }
+}
+
+/// Collects the changes to the variants of an enum.
+fn diff_variants(
+    old: &[VariantReport],
+    new: &[VariantReport],
+    direction: Direction,
+    changes: &mut Vec<(Severity, String)>,
+) {
+    let old_variants: BTreeMap<&str, &VariantReport> =
+        old.iter().map(|v| (v.wire_name.as_str(), v)).collect();
+    let new_variants: BTreeMap<&str, &VariantReport> =
+        new.iter().map(|v| (v.wire_name.as_str(), v)).collect();
+
+    for name in old_variants.keys() {
+        if !new_variants.contains_key(name) {
+            let severity = match direction.input {
+                true => Severity::Breaking,
+                false => Severity::NonBreaking,
+            };
+            changes.push((severity, format!("variant `{name}` removed")));
+        }
+    }
+    for (name, variant) in &new_variants {
+        let Some(before) = old_variants.get(name) else {
+            // Callers reading the enum may not handle the new variant.
+            let severity = match direction.output {
+                true => Severity::Breaking,
+                false => Severity::Additive,
+            };
+            changes.push((severity, format!("variant `{name}` added")));
+            continue;
+        };
+
+        if before.discriminant != variant.discriminant {
+            changes.push((
+                Severity::Breaking,
+                format!(
+                    "variant `{name}` changed from {} to {}",
+                    before.discriminant.unwrap_or_default(),
+                    variant.discriminant.unwrap_or_default()
+                ),
+            ));
+        }
+        if before.types != variant.types {
+            changes.push((
+                Severity::Breaking,
+                format!(
+                    "variant `{name}` changed from ({}) to ({})",
+                    before.types.join(", "),
+                    variant.types.join(", ")
+                ),
+            ));
+        }
+        diff_fields(
+            &before.fields,
+            &variant.fields,
+            direction,
+            &format!("variant `{name}` "),
+            changes,
+        );
+    }
+}
+
+/// Collects the changes to a model present in both snapshots.
+fn diff_model(
+    old: &ModelReport,
+    new: &ModelReport,
+    direction: Direction,
+    changes: &mut Vec<(Severity, String)>,
+) {
+    if old.kind != new.kind {
+        changes.push((
+            Severity::Breaking,
+            format!("changed from {} to {}", old.kind, new.kind),
+        ));
+        return;
+    }
+    if old.generics != new.generics {
+        changes.push((
+            Severity::Breaking,
+            format!(
+                "type parameters changed from <{}> to <{}>",
+                old.generics.join(", "),
+                new.generics.join(", ")
+            ),
+        ));
+    }
+    if old.rust_type != new.rust_type {
+        changes.push((
+            Severity::Breaking,
+            format!(
+                "changed from `{}` to `{}`",
+                old.rust_type.as_deref().unwrap_or_default(),
+                new.rust_type.as_deref().unwrap_or_default()
+            ),
+        ));
+    }
+    // Snapshots predating enum representations only had externally tagged enums.
+    let old_tagging = old.tagging.as_deref().unwrap_or("externally tagged");
+    let new_tagging = new.tagging.as_deref().unwrap_or("externally tagged");
+    if old_tagging != new_tagging {
+        changes.push((
+            Severity::Breaking,
+            format!("changed from {old_tagging} to {new_tagging}"),
+        ));
+    }
+    diff_fields(&old.fields, &new.fields, direction, "", changes);
+    diff_variants(&old.variants, &new.variants, direction, changes);
+    if old.deprecated.is_none() && new.deprecated.is_some() {
+        changes.push((Severity::NonBreaking, String::from("deprecated")));
+    }
+}
+
+/// Returns the active handlers by route, see `route_key`.
+fn routes(inspection: &Inspection) -> BTreeMap<String, &HandlerReport> {
+    let mut routes = BTreeMap::new();
+    for handler in inspection.handlers.iter().filter(|h| h.active) {
+        routes.entry(route_key(handler)).or_insert(handler);
+    }
+    routes
+}
+
+/// Returns the models by name, the first declared if several share one.
+fn models(inspection: &Inspection) -> BTreeMap<String, &ModelReport> {
+    let mut models = BTreeMap::new();
+    for model in &inspection.models {
+        models.entry(model.name.clone()).or_insert(model);
+    }
+    models
+}
+
+/// Compares two API snapshots, classifying every change by how it affects existing callers.
+///
+/// Routes are matched by method and path, regardless of the names of path parameters, so a
+/// moved or renamed handler isn't reported as removed. Handlers compiled out by `#[cfg(...)]`
+/// are left out. Models are matched by name, and fields and variants by their JSON name.
+///
+/// i.e. making the query parameter `page` of `GET /threads?<page>` required ->
+/// `breaking: GET /threads: parameter `page` is now required`
+///
+/// # Arguments
+///
+/// * `old` - The snapshot callers were built against.
+/// * `new` - The snapshot to check.
+///
+/// # Returns
+///
+/// The changes ordered by severity, then subject.
+pub fn api_diff(old: &Inspection, new: &Inspection) -> Vec<ApiChange> {
+    let mut changes = vec![];
+    let mut push = |subject: String, found: Vec<(Severity, String)>| {
+        changes.extend(found.into_iter().map(|(severity, description)| ApiChange {
+            severity,
+            subject: subject.clone(),
+            description,
+        }));
+    };
+
+    let old_routes = routes(old);
+    let new_routes = routes(new);
+    let keys: BTreeSet<&String> = old_routes.keys().chain(new_routes.keys()).collect();
+    for key in keys {
+        let mut found = vec![];
+        match (old_routes.get(key), new_routes.get(key)) {
+            (Some(_), None) => found.push((Severity::Breaking, String::from("route removed"))),
+            (None, Some(_)) => found.push((Severity::Additive, String::from("route added"))),
+            (Some(before), Some(after)) => diff_handler(before, after, &mut found),
+            (None, None) => {}
+        }
+        push(key.clone(), found);
+    }
+
+    let old_models = models(old);
+    let new_models = models(new);
+    let old_directions = model_directions(old);
+    let keys: BTreeSet<&String> = old_models.keys().chain(new_models.keys()).collect();
+    for key in keys {
+        let mut found = vec![];
+        match (old_models.get(key), new_models.get(key)) {
+            (Some(_), None) => found.push((Severity::Breaking, String::from("model removed"))),
+            (None, Some(_)) => found.push((Severity::Additive, String::from("model added"))),
+            (Some(before), Some(after)) => {
+                let direction = old_directions
+                    .get(key.as_str())
+                    .copied()
+                    .unwrap_or_default();
+                diff_model(before, after, direction, &mut found);
+            }
+            (None, None) => {}
+        }
+        push(format!("model {key}"), found);
+    }
+
+    changes.sort_by(|a, b| {
+        (a.severity, &a.subject, &a.description).cmp(&(b.severity, &b.subject, &b.description))
+    });
+    changes
+}
+
+/// Renders the changes with a line each and a closing count per severity.
+///
+/// i.e. `breaking: GET /threads: required parameter `page` added`
+pub fn diff_report(changes: &[ApiChange]) -> String {
+    if changes.is_empty() {
+        return String::from("No API changes");
+    }
+
+    let mut lines: Vec<String> = changes
+        .iter()
+        .map(|change| {
+            format!(
+                "{}: {}: {}",
+                change.severity.label(),
+                change.subject,
+                change.description
+            )
+        })
+        .collect();
+
+    let counts: Vec<String> = [
+        Severity::Breaking,
+        Severity::NonBreaking,
+        Severity::Additive,
+    ]
+    .iter()
+    .map(|severity| {
+        let count = changes.iter().filter(|c| c.severity == *severity).count();
+        format!("{count} {}", severity.label())
+    })
+    .collect();
+    lines.push(String::new());
+    lines.push(counts.join(", "));
+    lines.join("\n")
+}
@@ -0,0 +1,223 @@
+use clap::ValueEnum;
+
+use crate::generator::axios_client::axios_client;
+use crate::generator::fetch_client::fetch_client;
+use crate::generator::handler_members::handler_members;
+use crate::generator::model_declarations::model_declarations;
+use crate::generator::options::{ClientTarget, GeneratorOptions, HooksTarget};
+use crate::generator::query_hooks::query_hooks;
+use crate::generator::wire_conversion::{converted_models, wire_converters};
+use crate::parser::handler_names::Collision;
+use crate::parser::model::RustModel;
+use crate::parser::source_module::SourceModule;
+
+/// How the handlers of each source module are arranged in the generated output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Layout {
+    /// All handlers as members of the root interface, grouped by a file-name comment.
+    Flat,
+    /// One nested object type per source module, e.g. `api.thread.get_thread`.
+    Nested,
+    /// One output file per source module, plus an `index.ts` re-exporting them.
+    Files,
+}
+
+/// The comment every generated file starts with.
+pub fn header() -> String {
+    format!(
+        "/*\n * Generated by rocket-ts {} 🚀 🌎\n */\n",
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// Returns the models to emit declarations for.
+pub fn emitted_models<'a>(
+    modules: &'a [SourceModule],
+    options: &GeneratorOptions,
+) -> Vec<&'a RustModel> {
+    if options.models {
+        modules.iter().flat_map(|m| &m.models).collect()
+    } else {
+        vec![]
+    }
+}
+
+/// Generates the client requested in the options, along with its hooks and wire converters.
+fn client(modules: &[SourceModule], options: &GeneratorOptions) -> Option<String> {
+    let models = emitted_models(modules, options);
+    let converted = converted_models(&models, options);
+
+    let mut ts = match options.client? {
+        ClientTarget::Fetch => fetch_client(modules, options, &converted),
+        ClientTarget::Axios => axios_client(modules, options, &converted),
+    };
+    if let Some(HooksTarget::ReactQuery) = options.hooks {
+        ts.push_str(&query_hooks(modules, options));
+    }
+    ts.push_str(&wire_converters(&models, options, &converted));
+
+    Some(ts)
+}
+
+/// Generates the interface holding the handlers compiled out by `#[cfg(...)]`, if requested.
+///
+/// Its members are grouped by a file-name comment regardless of the layout.
+fn gated_interface(modules: &[SourceModule], options: &GeneratorOptions) -> Option<String> {
+    let name = options.gated_interface.as_ref()?;
+    if modules.iter().all(|m| m.gated_handlers.is_empty()) {
+        return None;
+    }
+
+    let mut ts = format!("\nexport interface {name} {{");
+    for module in modules.iter().filter(|m| !m.gated_handlers.is_empty()) {
+        ts.push_str(&format!("\n\t// {}\n", module.file_name));
+        ts.push_str(&handler_members(&module.gated_handlers, options, "\t"));
+    }
+    ts.push_str("}\n");
+
+    Some(ts)
+}
+
+/// Generates a single TypeScript file holding the root interface for all modules.
+///
+/// # Arguments
+///
+/// * `modules` - The handlers and models extracted from each source file.
+/// * `options` - The generator options, with either `Layout::Flat` or `Layout::Nested`.
+pub fn api_interface(modules: &[SourceModule], options: &GeneratorOptions) -> String {
+    let mut ts = header();
+    ts.push_str(&format!("export interface {} {{", options.interface_name));
+
+    for module in modules {
+        ts.push_str(&format!("\n\t// {}\n", module.file_name));
+        if options.layout == Layout::Nested {
+            ts.push_str(&format!("\t{}: {{\n", module.name));
+            ts.push_str(&handler_members(&module.handlers, options, "\t\t"));
+            ts.push_str("\t};\n");
+        } else {
+            ts.push_str(&handler_members(&module.handlers, options, "\t"));
+        }
+    }
+
+    ts.push_str("}\n");
+    if let Some(gated) = gated_interface(modules, options) {
+        ts.push_str(&gated);
+    }
+    ts.push_str(&model_declarations(
+        &emitted_models(modules, options),
+        options,
+    ));
+
+    if let Some(client) = client(modules, options) {
+        ts.push_str(&client);
+    }
+
+    ts
+}
+
+/// Returns the model names referenced by a piece of generated TypeScript.
+fn referenced_models<'a>(ts: &str, model_names: &[&'a str]) -> Vec<&'a str> {
+    let idents: Vec<&str> = ts
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .collect();
+
+    model_names
+        .iter()
+        .filter(|name| idents.contains(name))
+        .copied()
+        .collect()
+}
+
+/// The files `module_files` writes besides the module files, without their `.ts` extension.
+pub const RESERVED_FILES: [&str; 3] = ["index", "models", "client"];
+
+/// Finds the modules `module_files` can't write, as their file name is reserved, see
+/// `RESERVED_FILES`. Modules sharing a name are found by `find_module_collisions`.
+///
+/// # Returns
+///
+/// The conflicting module names with the path of the file declaring them.
+pub fn file_conflicts(modules: &[SourceModule]) -> Vec<Collision> {
+    modules
+        .iter()
+        .filter(|module| RESERVED_FILES.contains(&module.name.as_str()))
+        .map(|module| Collision {
+            name: module.name.clone(),
+            locations: vec![module.path.display().to_string()],
+        })
+        .collect()
+}
+
+/// Generates one TypeScript file per source module plus an `index.ts` re-exporting them.
+///
+/// Each module file exports an interface named after the module. The index declares the root
+/// interface with one member per module. Models, if requested, are emitted to `models.ts` and
+/// imported where referenced. Callers check `file_conflicts` first, so no file is overwritten.
+///
+/// # Returns
+///
+/// The file names and their contents.
+pub fn module_files(modules: &[SourceModule], options: &GeneratorOptions) -> Vec<(String, String)> {
+    let mut files = vec![];
+    let mut index = header();
+
+    let model_list = emitted_models(modules, options);
+    let model_names: Vec<&str> = model_list.iter().map(|m| m.name.as_str()).collect();
+    let import_models = |ts: &mut String, body: &str| {
+        let imports = referenced_models(body, &model_names);
+        if !imports.is_empty() {
+            ts.push_str(&format!(
+                "import type {{ {} }} from \"./models\";\n\n",
+                imports.join(", ")
+            ));
+        }
+    };
+
+    if options.models {
+        let mut ts = header();
+        ts.push_str(&model_declarations(&model_list, options));
+        files.push((String::from("models.ts"), ts));
+        index.push_str("export * from \"./models\";\n");
+    }
+
+    if let Some(client) = client(modules, options) {
+        let mut ts = header();
+        import_models(&mut ts, &client);
+        ts.push_str(client.trim_start());
+        files.push((String::from("client.ts"), ts));
+        index.push_str("export * from \"./client\";\n");
+    }
+
+    for module in modules {
+        let members = handler_members(&module.handlers, options, "\t");
+
+        let mut ts = header();
+        import_models(&mut ts, &members);
+        ts.push_str(&format!("// {}\n", module.file_name));
+        ts.push_str(&format!("export interface {} {{\n", module.name));
+        ts.push_str(&members);
+        ts.push_str("}\n");
+        files.push((format!("{}.ts", module.name), ts));
+
+        index.push_str(&format!(
+            "import type {{ {0} }} from \"./{0}\";\nexport type {{ {0} }};\n",
+            module.name
+        ));
+    }
+
+    index.push_str(&format!(
+        "\nexport interface {} {{\n",
+        options.interface_name
+    ));
+    for module in modules {
+        index.push_str(&format!("\t{0}: {0};\n", module.name));
+    }
+    index.push_str("}\n");
+    if let Some(gated) = gated_interface(modules, options) {
+        import_models(&mut index, &gated);
+        index.push_str(&gated);
+    }
+    files.push((String::from("index.ts"), index));
+
+    files
+}
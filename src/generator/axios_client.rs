@@ -0,0 +1,164 @@
+use std::collections::BTreeSet;
+
+use crate::generator::fetch_client::{
+    client_data_type, client_params, client_response_kind, client_signature, has_form_body,
+    request_body, request_path, response_decoder, FORM_RUNTIME, QUERY_RUNTIME,
+};
+use crate::generator::handler_members::{handler_comment, handler_name};
+use crate::generator::module_members::module_members;
+use crate::generator::options::GeneratorOptions;
+use crate::generator::tsdoc::tsdoc;
+use crate::generator::wire_conversion::call;
+use crate::parser::response_kind::ResponseKind;
+use crate::parser::source_module::SourceModule;
+use crate::parser::visitor::RocketReqHandler;
+
+/// The axios types used by the generated client functions.
+const RUNTIME: &str = r#"
+import type { AxiosInstance, AxiosResponse, ResponseType } from "axios";
+"#;
+
+/// The axios call shared by all generated client functions.
+const REQUEST_RUNTIME: &str = r#"
+function __request(
+	instance: AxiosInstance,
+	method: string,
+	url: string,
+	data?: unknown,
+	responseType: ResponseType = "json",
+): Promise<AxiosResponse> {
+	const json = data !== undefined && !(data instanceof URLSearchParams) && !(data instanceof FormData) && !(data instanceof Blob);
+	const headers = json ? { "Content-Type": "application/json" } : undefined;
+	return instance.request({ method, url, data, headers, responseType });
+}
+"#;
+
+/// Renders a handler as an async client function, resolving to the response data or to the
+/// whole `AxiosResponse`.
+fn client_function(
+    handler: &RocketReqHandler,
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+    raw: bool,
+    indent: &str,
+) -> String {
+    let name = handler_name(handler, options);
+    let kind = client_response_kind(handler);
+    if matches!(
+        kind,
+        ResponseKind::EventStream(_)
+            | ResponseKind::TextStream
+            | ResponseKind::ByteStream
+            | ResponseKind::WebSocket
+    ) {
+        // Browsers' axios adapter buffers the whole response, so streams can't be read.
+        return format!("{indent}// {name}: streaming responses are not supported by axios\n");
+    }
+
+    let params = client_params(handler, options);
+    let signature = client_signature(&params, options);
+
+    let mut args = vec![
+        String::from("__instance"),
+        format!("{:?}", handler.method.to_uppercase()),
+        request_path(handler, &params, options, converted),
+    ];
+    let response_type = match kind {
+        ResponseKind::Text | ResponseKind::Html => Some("\"text\""),
+        ResponseKind::Binary => Some("\"blob\""),
+        _ => None,
+    };
+    match (request_body(&params, options, converted), response_type) {
+        (Some(body), Some(response_type)) => args.extend([body, response_type.to_string()]),
+        (Some(body), None) => args.push(body),
+        (None, Some(response_type)) => {
+            args.extend([String::from("undefined"), response_type.to_string()])
+        }
+        (None, None) => {}
+    }
+
+    let data = client_data_type(handler);
+    let decoder = match kind {
+        ResponseKind::Json => response_decoder(handler, converted),
+        _ => None,
+    };
+
+    let mut ts = handler_comment(handler, indent);
+    ts.push_str(&tsdoc(&handler.docs, indent));
+    let returns = match raw {
+        true => format!("AxiosResponse<{data}>"),
+        false => data.clone(),
+    };
+    ts.push_str(&format!(
+        "{indent}{name}: async ({signature}): Promise<{returns}> => {{\n"
+    ));
+    let value = match (raw, &decoder) {
+        (true, Some(f)) => format!("{{ ...__response, data: {} }}", call(f, "__response.data")),
+        (true, None) => String::from("__response"),
+        (false, Some(f)) => call(f, "__response.data"),
+        (false, None) => String::from("__response.data"),
+    };
+    if raw || data != "void" {
+        ts.push_str(&format!(
+            "{indent}\tconst __response = await __request({});\n",
+            args.join(", ")
+        ));
+        ts.push_str(&format!("{indent}\treturn {value};\n"));
+    } else {
+        ts.push_str(&format!(
+            "{indent}\tawait __request({});\n",
+            args.join(", ")
+        ));
+    }
+    ts.push_str(&format!("{indent}}},\n"));
+    ts
+}
+
+/// Generates a client calling the handlers through an `AxiosInstance`.
+///
+/// `createClient(instance)` returns an object with one async function per handler resolving to
+/// the response data, arranged like the root interface. `createRawClient(instance)` has the same
+/// functions resolving to the whole `AxiosResponse`, for reading status and headers. Requests go
+/// through the given instance, so its interceptors apply, and non-2xx responses reject with an
+/// `AxiosError`. Form bodies are encoded like the fetch client's. Streaming and websocket
+/// handlers are left out, as axios buffers responses in browsers. Like the fetch client's, the
+/// runtime helpers and locals are prefixed with `__`.
+///
+/// # Arguments
+///
+/// * `modules` - The handlers extracted from each source file.
+/// * `options` - The generator options.
+/// * `converted` - The models needing conversion, see `converted_models`.
+pub fn axios_client(
+    modules: &[SourceModule],
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+) -> String {
+    let mut ts = String::from(RUNTIME);
+    ts.push_str(QUERY_RUNTIME);
+    ts.push_str(REQUEST_RUNTIME);
+    if has_form_body(modules, options) {
+        ts.push_str(FORM_RUNTIME);
+    }
+
+    ts.push_str("\nexport function createClient(__instance: AxiosInstance) {\n\treturn {\n");
+    ts.push_str(&module_members(
+        modules,
+        options.layout,
+        |_, handler, indent| client_function(handler, options, converted, false, indent),
+    ));
+    ts.push_str("\t};\n}\n");
+
+    ts.push_str("\nexport function createRawClient(__instance: AxiosInstance) {\n\treturn {\n");
+    ts.push_str(&module_members(
+        modules,
+        options.layout,
+        |_, handler, indent| client_function(handler, options, converted, true, indent),
+    ));
+    ts.push_str("\t};\n}\n");
+
+    ts.push_str("\nexport type Client = ReturnType<typeof createClient>;\n");
+    ts.push_str("export type RawClient = ReturnType<typeof createRawClient>;\n");
+
+    ts
+}
@@ -0,0 +1,458 @@
+use std::collections::BTreeSet;
+
+use crate::generator::handler_members::{handler_comment, handler_name, handler_return_type};
+use crate::generator::module_members::module_members;
+use crate::generator::options::GeneratorOptions;
+use crate::generator::tsdoc::tsdoc;
+use crate::generator::wire_conversion::{call, converter, Direction};
+use crate::parser::handler_params::{
+    handler_params, is_omittable, BodyFormat, HandlerParam, ParamKind,
+};
+use crate::parser::response_kind::{response_kind, ResponseKind};
+use crate::parser::response_type::response_type;
+use crate::parser::source_module::SourceModule;
+use crate::parser::ts_type::ts_type;
+use crate::parser::visitor::RocketReqHandler;
+
+/// The configuration and error types shared by all generated client functions.
+const RUNTIME: &str = r#"
+export interface ClientConfig {
+	/** Prepended to every route, e.g. "https://api.example.com". */
+	baseUrl: string;
+	/** Merged into every request, e.g. to send credentials or extra headers. */
+	init?: RequestInit;
+}
+
+/** Thrown when Rocket responds with a status outside of 200-299. */
+export class ApiError extends Error {
+	readonly response: Response;
+
+	constructor(response: Response) {
+		super(`${response.url} responded with ${response.status} ${response.statusText}`);
+		this.response = response;
+	}
+}
+"#;
+
+/// The `fetch` call shared by all generated client functions.
+const REQUEST_RUNTIME: &str = r#"
+async function __request(config: ClientConfig, method: string, path: string, body?: unknown): Promise<Response> {
+	const init: RequestInit = { ...config.init, method };
+	if (body instanceof URLSearchParams || body instanceof FormData || body instanceof Blob) {
+		init.body = body;
+	} else if (body !== undefined) {
+		const headers = new Headers(config.init?.headers);
+		headers.set("Content-Type", "application/json");
+		init.headers = headers;
+		init.body = JSON.stringify(body);
+	}
+	const response = await fetch(config.baseUrl + path, init);
+	if (!response.ok) throw new ApiError(response);
+	return response;
+}
+"#;
+
+/// Helper appending query parameters to a request path, see `request_path`.
+pub const QUERY_RUNTIME: &str = r#"
+function __query(params: Record<string, unknown>): string {
+	const search = new URLSearchParams();
+	for (const [key, value] of Object.entries(params)) {
+		for (const item of Array.isArray(value) ? value : [value]) {
+			if (item !== undefined && item !== null) search.append(key, String(item));
+		}
+	}
+	const qs = search.toString();
+	return qs ? `?${qs}` : "";
+}
+"#;
+
+/// Helper encoding form bodies, emitted if any handler reads one, see `request_body`.
+///
+/// Nested fields are named with dots and list items repeat their name, or are indexed if they are
+/// structs, as Rocket's `FromForm` expects. Bodies holding a `Blob` are sent as multipart.
+pub const FORM_RUNTIME: &str = r#"
+function __form(value: object): URLSearchParams | FormData {
+	const entries: [string, string | Blob][] = [];
+	const visit = (key: string, item: unknown) => {
+		if (item === undefined || item === null) return;
+		if (item instanceof Blob) {
+			entries.push([key, item]);
+		} else if (Array.isArray(item)) {
+			item.forEach((v, i) => visit(typeof v === "object" && !(v instanceof Blob) ? `${key}[${i}]` : key, v));
+		} else if (typeof item === "object") {
+			for (const [k, v] of Object.entries(item)) visit(key ? `${key}.${k}` : k, v);
+		} else {
+			entries.push([key, String(item)]);
+		}
+	};
+	visit("", value);
+	if (entries.some(([, v]) => v instanceof Blob)) {
+		const data = new FormData();
+		for (const [k, v] of entries) data.append(k, v);
+		return data;
+	}
+	return new URLSearchParams(entries as [string, string][]);
+}
+"#;
+
+/// Helpers reading streamed responses, emitted if any handler streams.
+const STREAM_RUNTIME: &str = r#"
+async function* __text(response: Response): AsyncGenerator<string> {
+	const reader = response.body!.pipeThrough(new TextDecoderStream()).getReader();
+	for (;;) {
+		const { done, value } = await reader.read();
+		if (done) return;
+		yield value;
+	}
+}
+
+async function* __chunks(response: Response): AsyncGenerator<Uint8Array> {
+	const reader = response.body!.getReader();
+	for (;;) {
+		const { done, value } = await reader.read();
+		if (done) return;
+		yield value;
+	}
+}
+
+/** Yields the data of each server-sent event. */
+async function* __events(response: Response): AsyncGenerator<string> {
+	let buffer = "";
+	let data: string[] = [];
+	for await (const chunk of __text(response)) {
+		buffer += chunk;
+		const lines = buffer.split("\n");
+		buffer = lines.pop()!;
+		for (let line of lines) {
+			if (line.endsWith("\r")) line = line.slice(0, -1);
+			if (line === "") {
+				if (data.length > 0) yield data.join("\n");
+				data = [];
+			} else if (line === "data" || line.startsWith("data:")) {
+				const value = line.slice(5);
+				data.push(value.startsWith(" ") ? value.slice(1) : value);
+			}
+		}
+	}
+}
+"#;
+
+/// Helper connecting to websocket handlers, emitted if there are any.
+const SOCKET_RUNTIME: &str = r#"
+function __socketUrl(config: ClientConfig, path: string): string {
+	const url = new URL(config.baseUrl + path, globalThis.location?.href);
+	url.protocol = url.protocol === "https:" ? "wss:" : "ws:";
+	return url.toString();
+}
+"#;
+
+/// Renders the request path of a handler as a template literal.
+///
+/// i.e. "/thread/<kid>/comments?<page>&lang=en" -> `/thread/${encodeURIComponent(String(kid))}/comments${__query({ lang: "en", page })}`
+pub fn request_path(
+    handler: &RocketReqHandler,
+    params: &[HandlerParam],
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+) -> String {
+    let (path, query) = handler
+        .path
+        .split_once('?')
+        .unwrap_or((handler.path.as_str(), ""));
+
+    let mut ts = String::from("`");
+    for (i, segment) in path.split('/').enumerate() {
+        if i > 0 {
+            ts.push('/');
+        }
+        match segment.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+            Some(name) => match name.strip_suffix("..") {
+                Some(name) => ts.push_str(&format!(
+                    "${{String({}).split(\"/\").map(encodeURIComponent).join(\"/\")}}",
+                    options.name(name)
+                )),
+                None => ts.push_str(&format!(
+                    "${{encodeURIComponent(String({}))}}",
+                    options.name(name)
+                )),
+            },
+            None => ts.push_str(segment),
+        }
+    }
+
+    let mut entries = vec![];
+    for item in query.split('&').filter(|s| !s.is_empty()) {
+        if let Some((key, value)) = item.split_once('=') {
+            entries.push(format!("{key:?}: {value:?}"));
+        }
+    }
+    for param in params {
+        let name = options.name(&param.name);
+        match param.kind {
+            ParamKind::Query => entries.push(format!("{:?}: {name}", param.name)),
+            ParamKind::QueryFields => match converter(&param.ty, Direction::Encode, converted) {
+                Some(f) => entries.push(format!("...{}", call(&f, &name))),
+                None => entries.push(format!("...{name}")),
+            },
+            _ => {}
+        }
+    }
+    if !entries.is_empty() {
+        ts.push_str(&format!("${{__query({{ {} }})}}", entries.join(", ")));
+    }
+
+    ts.push('`');
+    ts
+}
+
+/// Returns the parameters callers pass to a client function, i.e. all but request guards.
+pub fn client_params(handler: &RocketReqHandler, options: &GeneratorOptions) -> Vec<HandlerParam> {
+    handler_params(handler, &options.exclusion_list, &options.url_types)
+        .into_iter()
+        .filter(|p| p.kind != ParamKind::Guard)
+        .collect()
+}
+
+/// Renders the parameter list of a client function.
+///
+/// i.e. `thread_id: number, page?: number`
+pub fn client_signature(params: &[HandlerParam], options: &GeneratorOptions) -> String {
+    let signature: Vec<String> = params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let name = options.name(&param.name);
+            match (param.optional, is_omittable(params, i)) {
+                (true, true) => format!("{name}?: {}", param.ts_type),
+                (true, false) => format!("{name}: {} | undefined", param.ts_type),
+                _ => format!("{name}: {}", param.ts_type),
+            }
+        })
+        .collect();
+    signature.join(", ")
+}
+
+/// Renders the request body of a client function, converted to its wire names if needed and
+/// encoded with `__form` for form data guards. MessagePack bodies are sent as the caller encoded
+/// them.
+pub fn request_body(
+    params: &[HandlerParam],
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+) -> Option<String> {
+    let body = params.iter().find(|p| p.kind == ParamKind::Body)?;
+    let name = options.name(&body.name);
+    if body.body_format == Some(BodyFormat::MsgPack) {
+        return Some(format!(
+            "new Blob([{name}], {{ type: \"application/msgpack\" }})"
+        ));
+    }
+    let value = match converter(&body.ty, Direction::Encode, converted) {
+        Some(f) => call(&f, &name),
+        None => name,
+    };
+    match body.body_format {
+        Some(BodyFormat::Form) => Some(format!("__form({value})")),
+        _ => Some(value),
+    }
+}
+
+/// Returns true if any handler reads a form body, so the client needs `FORM_RUNTIME`.
+pub fn has_form_body(modules: &[SourceModule], options: &GeneratorOptions) -> bool {
+    modules.iter().flat_map(|m| &m.handlers).any(|handler| {
+        client_params(handler, options)
+            .iter()
+            .any(|p| p.body_format == Some(BodyFormat::Form))
+    })
+}
+
+/// Returns how a client reads the response of a handler, where `#[rocket_ts(returns = "void")]`
+/// makes it ignore the body.
+pub fn client_response_kind(handler: &RocketReqHandler) -> ResponseKind {
+    match (&handler.returns, response_kind(handler)) {
+        (Some(returns), _) if returns == "void" => ResponseKind::Empty,
+        (_, kind) => kind,
+    }
+}
+
+/// Returns the TypeScript type a client function resolves to, i.e. `void` for redirects and
+/// empty responses.
+pub fn client_data_type(handler: &RocketReqHandler) -> String {
+    match client_response_kind(handler) {
+        ResponseKind::Redirect | ResponseKind::Empty => String::from("void"),
+        _ => handler_return_type(handler),
+    }
+}
+
+/// Returns the function converting a JSON response from its wire names, if needed.
+pub fn response_decoder(
+    handler: &RocketReqHandler,
+    converted: &BTreeSet<String>,
+) -> Option<String> {
+    response_type(&handler.return_type)
+        .filter(|_| handler.returns.is_none())
+        .and_then(|ty| converter(&ty, Direction::Decode, converted))
+}
+
+/// Renders a handler as an async client function.
+fn client_function(
+    handler: &RocketReqHandler,
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+    indent: &str,
+) -> String {
+    let params = client_params(handler, options);
+    let signature = client_signature(&params, options);
+
+    let mut args = vec![
+        String::from("__config"),
+        format!("{:?}", handler.method.to_uppercase()),
+        request_path(handler, &params, options, converted),
+    ];
+    args.extend(request_body(&params, options, converted));
+
+    let mut ts = handler_comment(handler, indent);
+    ts.push_str(&tsdoc(&handler.docs, indent));
+
+    let kind = client_response_kind(handler);
+    if let ResponseKind::WebSocket = kind {
+        ts.push_str(&format!(
+            "{indent}{}: ({signature}): WebSocket => new WebSocket(__socketUrl(__config, {})),\n",
+            handler_name(handler, options),
+            request_path(handler, &params, options, converted)
+        ));
+        return ts;
+    }
+
+    let stream = match &kind {
+        ResponseKind::WebSocket
+        | ResponseKind::Json
+        | ResponseKind::Text
+        | ResponseKind::Html
+        | ResponseKind::Binary
+        | ResponseKind::Redirect
+        | ResponseKind::Empty => None,
+        ResponseKind::EventStream(Some(event)) => {
+            let data = match converter(event, Direction::Decode, converted) {
+                Some(f) => call(&f, "JSON.parse(data)"),
+                None => String::from("JSON.parse(data)"),
+            };
+            Some((
+                ts_type(event),
+                format!("for await (const data of __events(__response)) yield {data};"),
+            ))
+        }
+        ResponseKind::EventStream(None) => Some((
+            String::from("string"),
+            String::from("yield* __events(__response);"),
+        )),
+        ResponseKind::TextStream => Some((
+            String::from("string"),
+            String::from("yield* __text(__response);"),
+        )),
+        ResponseKind::ByteStream => Some((
+            String::from("Uint8Array"),
+            String::from("yield* __chunks(__response);"),
+        )),
+    };
+
+    if let Some((item, body)) = stream {
+        ts.push_str(&format!(
+            "{indent}{}: async function* ({signature}): AsyncGenerator<{item}> {{\n",
+            handler_name(handler, options),
+        ));
+        ts.push_str(&format!(
+            "{indent}\tconst __response = await __request({});\n",
+            args.join(", ")
+        ));
+        ts.push_str(&format!("{indent}\t{body}\n"));
+    } else if matches!(kind, ResponseKind::Redirect | ResponseKind::Empty) {
+        ts.push_str(&format!(
+            "{indent}{}: async ({signature}): Promise<void> => {{\n",
+            handler_name(handler, options),
+        ));
+        ts.push_str(&format!(
+            "{indent}\tawait __request({});\n",
+            args.join(", ")
+        ));
+    } else {
+        ts.push_str(&format!(
+            "{indent}{}: async ({signature}): Promise<{}> => {{\n",
+            handler_name(handler, options),
+            handler_return_type(handler)
+        ));
+        ts.push_str(&format!(
+            "{indent}\tconst __response = await __request({});\n",
+            args.join(", ")
+        ));
+        let body = match kind {
+            ResponseKind::Text | ResponseKind::Html => String::from("__response.text()"),
+            ResponseKind::Binary => String::from("__response.blob()"),
+            _ => match response_decoder(handler, converted) {
+                Some(f) => call(&f, "await __response.json()"),
+                None => String::from("__response.json()"),
+            },
+        };
+        ts.push_str(&format!("{indent}\treturn {body};\n"));
+    }
+
+    ts.push_str(&format!("{indent}}},\n"));
+    ts
+}
+
+/// Generates a client calling the handlers with `fetch`.
+///
+/// `createClient(config)` returns an object with one async function per handler, arranged like
+/// the root interface. Form bodies are sent url-encoded, or as multipart if they hold files, and
+/// other bodies as JSON. Responses are read as JSON, text or a `Blob` by their content kind, see
+/// `ResponseKind`. Streaming handlers become async generators of their items, websocket
+/// handlers open a `WebSocket`. Values are converted with the `decode<Model>` and `encode<Model>`
+/// functions where a naming convention renamed model fields. The runtime helpers and locals are
+/// prefixed with `__`, so handler parameters in scope of the same functions can't shadow them.
+///
+/// # Arguments
+///
+/// * `modules` - The handlers extracted from each source file.
+/// * `options` - The generator options.
+/// * `converted` - The models needing conversion, see `converted_models`.
+pub fn fetch_client(
+    modules: &[SourceModule],
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+) -> String {
+    let mut ts = String::from(RUNTIME);
+    ts.push_str(QUERY_RUNTIME);
+    ts.push_str(REQUEST_RUNTIME);
+    if has_form_body(modules, options) {
+        ts.push_str(FORM_RUNTIME);
+    }
+    let kinds: Vec<ResponseKind> = modules
+        .iter()
+        .flat_map(|m| &m.handlers)
+        .map(response_kind)
+        .collect();
+    if kinds.iter().any(|kind| {
+        matches!(
+            kind,
+            ResponseKind::EventStream(_) | ResponseKind::TextStream | ResponseKind::ByteStream
+        )
+    }) {
+        ts.push_str(STREAM_RUNTIME);
+    }
+    if kinds
+        .iter()
+        .any(|kind| matches!(kind, ResponseKind::WebSocket))
+    {
+        ts.push_str(SOCKET_RUNTIME);
+    }
+
+    ts.push_str("\nexport function createClient(__config: ClientConfig) {\n\treturn {\n");
+    ts.push_str(&module_members(
+        modules,
+        options.layout,
+        |_, handler, indent| client_function(handler, options, converted, indent),
+    ));
+    ts.push_str("\t};\n}\n\nexport type Client = ReturnType<typeof createClient>;\n");
+
+    ts
+}
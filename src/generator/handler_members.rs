@@ -0,0 +1,106 @@
+use syn::{ReturnType, Type};
+
+use crate::generator::options::GeneratorOptions;
+use crate::generator::tsdoc::tsdoc;
+use crate::parser::cfg_predicate::cfg_display;
+use crate::parser::handler_params::handler_params;
+use crate::parser::params_as_comma_seperated::params_as_comma_separated_str;
+use crate::parser::response_kind::{response_kind, ResponseKind};
+use crate::parser::response_type::response_type;
+use crate::parser::ts_type::ts_type;
+use crate::parser::visitor::RocketReqHandler;
+
+/// Provide the inner return type of a request handler that TypeScript callers will care about.
+///
+/// i.e. Result<Json<Message>> -> "Message", Json<Paginated<Vec<Comment>>> -> "Paginated<Comment[]>"
+pub fn inner_return_type(node: &ReturnType) -> String {
+    match response_type(node) {
+        Some(Type::Tuple(tuple)) if tuple.elems.is_empty() => String::from("any"),
+        Some(ty) => ts_type(&ty),
+        None => String::from("any"), // Return types that don't match the assumption of a single TypeShare'd type.
+    }
+}
+
+/// Returns the TypeScript type a handler resolves to.
+///
+/// The type given with `#[rocket_ts(returns = "...")]`, or else the one derived from the return
+/// type. Text and HTML resolve to `string`, bytes and files to `Blob`, redirects and empty
+/// responses to `void`. Streams resolve to an `AsyncIterable` of their items, i.e. `AsyncIterable<Message>` for
+/// an `EventStream![]` declaring `#[rocket_ts(event = "Message")]`.
+pub fn handler_return_type(handler: &RocketReqHandler) -> String {
+    if let Some(returns) = &handler.returns {
+        return returns.clone();
+    }
+
+    match response_kind(handler) {
+        ResponseKind::Json => inner_return_type(&handler.return_type),
+        ResponseKind::Text | ResponseKind::Html => String::from("string"),
+        ResponseKind::Binary => String::from("Blob"),
+        ResponseKind::Redirect | ResponseKind::Empty => String::from("void"),
+        ResponseKind::EventStream(Some(event)) => format!("AsyncIterable<{}>", ts_type(&event)),
+        ResponseKind::EventStream(None) | ResponseKind::TextStream => {
+            String::from("AsyncIterable<string>")
+        }
+        ResponseKind::ByteStream => String::from("AsyncIterable<Uint8Array>"),
+        ResponseKind::WebSocket => String::from("WebSocket"),
+    }
+}
+
+/// Returns the comment introducing a handler, i.e. `// handler "/thread/<id>"`.
+///
+/// Websocket handlers are flagged as `// websocket "/chat"`, since callers connect to them
+/// rather than sending a request.
+pub fn handler_comment(handler: &RocketReqHandler, indent: &str) -> String {
+    match response_kind(handler) {
+        ResponseKind::WebSocket => format!("{indent}// websocket \"{}\"\n", handler.path),
+        _ => format!("{indent}// handler \"{}\"\n", handler.path),
+    }
+}
+
+/// Returns the name a handler is emitted under.
+///
+/// Names given explicitly with `#[rocket_ts(rename = "...")]` are kept verbatim, all others
+/// follow the configured naming convention.
+pub fn handler_name(handler: &RocketReqHandler, options: &GeneratorOptions) -> String {
+    if handler.explicit_name {
+        handler.name.clone()
+    } else {
+        options.name(&handler.name)
+    }
+}
+
+/// Renders request handlers as members of a TypeScript interface.
+///
+/// Each handler becomes a function-typed member preceded by its route and documentation.
+///
+/// # Arguments
+///
+/// * `handlers` - The request handlers to render.
+/// * `options` - The generator options.
+/// * `indent` - The indentation to prefix each line with.
+pub fn handler_members(
+    handlers: &[RocketReqHandler],
+    options: &GeneratorOptions,
+    indent: &str,
+) -> String {
+    let mut ts = String::new();
+
+    for handler in handlers {
+        let params = handler_params(handler, &options.exclusion_list, &options.url_types);
+        let params = params_as_comma_separated_str(&params, options.naming);
+        let return_type = handler_return_type(handler);
+        ts.push_str(&handler_comment(handler, indent));
+        if !handler.cfg.is_empty() {
+            ts.push_str(&format!("{indent}// {}\n", cfg_display(&handler.cfg)));
+        }
+        ts.push_str(&tsdoc(&handler.docs, indent));
+        ts.push_str(&format!(
+            "{indent}{}: ({}) => {};\n",
+            handler_name(handler, options),
+            params,
+            return_type
+        ));
+    }
+
+    ts
+}
@@ -0,0 +1,415 @@
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind};
+
+use quote::ToTokens;
+use serde::{Deserialize, Serialize};
+use syn::{FnArg, Pat, Type};
+
+use crate::generator::handler_members::handler_return_type;
+use crate::parser::doc_comments::Docs;
+use crate::parser::handler_params::{handler_params, param_kind, ParamKind};
+use crate::parser::model::{ModelField, ModelKind, ModelVariant, RustModel, VariantFields};
+use crate::parser::response_kind::{response_kind, ResponseKind};
+use crate::parser::response_type::{error_type, response_type};
+use crate::parser::source_module::SourceModule;
+use crate::parser::ts_type::ts_type;
+use crate::parser::visitor::RocketReqHandler;
+
+/// The version of the `inspect --json` format, bumped on incompatible changes.
+const FORMAT_VERSION: u32 = 1;
+
+/// The handlers and models found in the input, as reported by `inspect`.
+#[derive(Serialize, Deserialize)]
+pub struct Inspection {
+    /// The version of the format, see `FORMAT_VERSION`.
+    pub version: u32,
+    /// The handlers in source order, including those compiled out by `#[cfg(...)]`.
+    pub handlers: Vec<HandlerReport>,
+    /// The models in source order.
+    pub models: Vec<ModelReport>,
+}
+
+/// A request handler as reported by `inspect`.
+#[derive(Serialize, Deserialize)]
+pub struct HandlerReport {
+    /// The name of the handler, after `#[rocket_ts(rename = "...")]`.
+    pub name: String,
+    /// The fully qualified Rust path, i.e. `crate::routes::thread::get_thread`.
+    pub rust_path: String,
+    /// The HTTP method in upper case, i.e. `GET`.
+    pub method: String,
+    /// The route as written in the attribute, i.e. `/thread/<id>?<page>`.
+    pub uri: String,
+    /// The source file declaring the handler.
+    pub file: String,
+    /// The line of the handler's function name, i.e. of `get_thread` in `async fn get_thread(`.
+    pub line: usize,
+    /// False for handlers compiled out by `#[cfg(...)]`.
+    pub active: bool,
+    /// The `#[cfg(...)]` predicates the handler is compiled under.
+    pub cfg: Vec<String>,
+    /// The lines of the doc comment.
+    pub docs: Vec<String>,
+    /// The note of a `#[deprecated]` attribute, if any.
+    pub deprecated: Option<String>,
+    /// Every parameter in declaration order, including request guards.
+    pub params: Vec<ParamReport>,
+    /// What the handler responds with.
+    pub response: ResponseReport,
+}
+
+/// A handler parameter as reported by `inspect`.
+#[derive(Serialize, Deserialize)]
+pub struct ParamReport {
+    /// The name of the parameter.
+    pub name: String,
+    /// Where the value comes from in the request.
+    pub kind: ParamKind,
+    /// The Rust type in canonical form, with `type` aliases expanded and paths reduced to the name
+    /// they resolve to, i.e. `Vec<Thread>` for `Threads` or `Vec<models::Thread>`, see
+    /// `SymbolTable::canonicalize`.
+    pub rust_type: String,
+    /// None for parameters left out of the signature, i.e. request guards.
+    pub ts_type: Option<String>,
+    /// True for query parameters callers may leave out.
+    pub optional: bool,
+}
+
+/// The response of a handler as reported by `inspect`.
+#[derive(Serialize, Deserialize)]
+pub struct ResponseReport {
+    /// How callers consume the response, i.e. `json` or `event_stream`.
+    pub kind: String,
+    /// The Rust type of the serialized value, i.e. `Thread` for `Result<Json<Thread>, Error>`.
+    pub rust_type: Option<String>,
+    /// The TypeScript type the generated interface returns.
+    pub ts_type: String,
+    /// The Rust type of the error of a `Result`.
+    pub error_type: Option<String>,
+}
+
+/// A model as reported by `inspect`.
+#[derive(Serialize, Deserialize)]
+pub struct ModelReport {
+    /// The name of the model.
+    pub name: String,
+    /// The names of its type parameters.
+    pub generics: Vec<String>,
+    /// The fully qualified Rust path, i.e. `crate::models::Thread`.
+    pub rust_path: String,
+    /// The source file declaring the model.
+    pub file: String,
+    /// The shape of the model: `struct`, `newtype`, `enum` or `numeric_enum`.
+    pub kind: String,
+    /// The lines of the doc comment.
+    pub docs: Vec<String>,
+    /// The note of a `#[deprecated]` attribute, if any.
+    pub deprecated: Option<String>,
+    /// The inner type of a newtype struct.
+    pub rust_type: Option<String>,
+    /// The fields of a struct.
+    pub fields: Vec<FieldReport>,
+    /// The variants of an enum.
+    pub variants: Vec<VariantReport>,
+    /// How the variants of an enum are told apart, i.e. `internally tagged by "type"`, see
+    /// `EnumTagging`.
+    pub tagging: Option<String>,
+}
+
+/// A field of a struct or struct variant as reported by `inspect`.
+#[derive(Serialize, Deserialize)]
+pub struct FieldReport {
+    /// The name of the field in Rust.
+    pub name: String,
+    /// The name of the field in JSON, after serde's renaming.
+    pub wire_name: String,
+    /// True if the field may be missing from the JSON.
+    pub optional: bool,
+    /// The Rust type, resolved like `ParamReport::rust_type`.
+    pub rust_type: String,
+    /// The TypeScript type of the field.
+    pub ts_type: String,
+    /// The lines of the doc comment.
+    pub docs: Vec<String>,
+    /// The note of a `#[deprecated]` attribute, if any.
+    pub deprecated: Option<String>,
+}
+
+/// An enum variant as reported by `inspect`.
+#[derive(Serialize, Deserialize)]
+pub struct VariantReport {
+    /// The name of the variant in Rust.
+    pub name: String,
+    /// The name of the variant in JSON, after serde's renaming.
+    pub wire_name: String,
+    /// The value of a numeric enum variant.
+    pub discriminant: Option<i64>,
+    /// The lines of the doc comment.
+    pub docs: Vec<String>,
+    /// The note of a `#[deprecated]` attribute, if any.
+    pub deprecated: Option<String>,
+    /// The types of a tuple variant's fields.
+    pub types: Vec<String>,
+    /// The fields of a struct variant.
+    pub fields: Vec<FieldReport>,
+}
+
+/// Renders a Rust type without the spacing between tokens, i.e. `Vec<Comment>` rather than
+/// `Vec < Comment >`.
+fn rust_type(ty: &impl ToTokens) -> String {
+    let mut rendered = ty.to_token_stream().to_string();
+    for (spaced, tight) in [
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (":: ", "::"),
+        (" ::", "::"),
+        ("& ", "&"),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ;", ";"),
+    ] {
+        rendered = rendered.replace(spaced, tight);
+    }
+    rendered
+}
+
+/// Returns the name of a response kind, i.e. `event_stream`.
+fn kind_name(kind: &ResponseKind) -> &'static str {
+    match kind {
+        ResponseKind::Json => "json",
+        ResponseKind::Text => "text",
+        ResponseKind::Binary => "binary",
+        ResponseKind::Html => "html",
+        ResponseKind::Redirect => "redirect",
+        ResponseKind::Empty => "empty",
+        ResponseKind::EventStream(_) => "event_stream",
+        ResponseKind::TextStream => "text_stream",
+        ResponseKind::ByteStream => "byte_stream",
+        ResponseKind::WebSocket => "websocket",
+    }
+}
+
+fn field_report(field: &ModelField) -> FieldReport {
+    FieldReport {
+        name: field.name.clone(),
+        wire_name: field.wire_name.clone(),
+        optional: field.optional,
+        rust_type: rust_type(&field.ty),
+        ts_type: ts_type(&field.ty),
+        docs: field.docs.lines.clone(),
+        deprecated: field.docs.deprecated.clone(),
+    }
+}
+
+fn variant_report(variant: &ModelVariant) -> VariantReport {
+    let (types, fields) = match &variant.fields {
+        VariantFields::Unit => (vec![], vec![]),
+        VariantFields::Tuple(types) => (types.iter().map(rust_type).collect(), vec![]),
+        VariantFields::Named(fields) => (vec![], fields.iter().map(field_report).collect()),
+    };
+
+    VariantReport {
+        name: variant.name.clone(),
+        wire_name: variant.wire_name.clone(),
+        discriminant: variant.discriminant,
+        docs: variant.docs.lines.clone(),
+        deprecated: variant.docs.deprecated.clone(),
+        types,
+        fields,
+    }
+}
+
+fn model_report(model: &RustModel, file: &str) -> ModelReport {
+    let Docs { lines, deprecated } = &model.docs;
+    let mut report = ModelReport {
+        name: model.name.clone(),
+        generics: model.generics.clone(),
+        rust_path: [
+            model.module_path.as_slice(),
+            std::slice::from_ref(&model.name),
+        ]
+        .concat()
+        .join("::"),
+        file: file.to_string(),
+        kind: String::from("struct"),
+        docs: lines.clone(),
+        deprecated: deprecated.clone(),
+        rust_type: None,
+        fields: vec![],
+        variants: vec![],
+        tagging: None,
+    };
+
+    match &model.kind {
+        ModelKind::Struct(fields) => report.fields = fields.iter().map(field_report).collect(),
+        ModelKind::Newtype(inner) => {
+            report.kind = String::from("newtype");
+            report.rust_type = Some(rust_type(&**inner));
+        }
+        ModelKind::Enum(variants, tagging) => {
+            report.kind = String::from("enum");
+            report.variants = variants.iter().map(variant_report).collect();
+            report.tagging = Some(tagging.to_string());
+        }
+        ModelKind::NumericEnum(variants) => {
+            report.kind = String::from("numeric_enum");
+            report.variants = variants.iter().map(variant_report).collect();
+        }
+    }
+    report
+}
+
+fn handler_report(
+    handler: &RocketReqHandler,
+    file: &str,
+    active: bool,
+    exclusion_list: &[String],
+    url_types: &BTreeMap<String, String>,
+) -> HandlerReport {
+    let included = handler_params(handler, exclusion_list, url_types);
+    let params = handler
+        .params
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(pat_type) => match &*pat_type.pat {
+                Pat::Ident(pat_ident) => Some((pat_ident.ident.to_string(), &*pat_type.ty)),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        })
+        .map(|(name, ty): (String, &Type)| {
+            let param = included.iter().find(|param| param.name == name);
+            ParamReport {
+                kind: param_kind(handler, &name),
+                rust_type: rust_type(ty),
+                ts_type: param.map(|param| param.ts_type.clone()),
+                optional: param.is_some_and(|param| param.optional),
+                name,
+            }
+        })
+        .collect();
+
+    HandlerReport {
+        name: handler.name.clone(),
+        rust_path: handler.rust_path.clone(),
+        method: handler.method.to_uppercase(),
+        uri: handler.path.clone(),
+        file: file.to_string(),
+        line: handler.line,
+        active,
+        cfg: handler.cfg.clone(),
+        docs: handler.docs.lines.clone(),
+        deprecated: handler.docs.deprecated.clone(),
+        params,
+        response: ResponseReport {
+            kind: kind_name(&response_kind(handler)).to_string(),
+            rust_type: response_type(&handler.return_type).map(|ty| rust_type(&ty)),
+            ts_type: handler_return_type(handler),
+            error_type: error_type(&handler.return_type).map(|ty| rust_type(&ty)),
+        },
+    }
+}
+
+/// Collects what was extracted from the input, with the types resolved as in the generated code.
+///
+/// Every handler is reported with its classified parameters, including the request guards left
+/// out of the signatures, and its response and error types. Handlers compiled out by
+/// `#[cfg(...)]` are reported as inactive.
+///
+/// # Arguments
+///
+/// * `modules` - The handlers and models extracted from each source file.
+/// * `exclusion_list` - The parameter types left out of the signatures.
+/// * `url_types` - The TypeScript types of custom URL value types, see `GeneratorOptions`.
+pub fn inspection(
+    modules: &[SourceModule],
+    exclusion_list: &[String],
+    url_types: &BTreeMap<String, String>,
+) -> Inspection {
+    let mut handlers = vec![];
+    let mut models = vec![];
+    for module in modules {
+        let file = module.path.display().to_string();
+        for handler in &module.handlers {
+            handlers.push(handler_report(
+                handler,
+                &file,
+                true,
+                exclusion_list,
+                url_types,
+            ));
+        }
+        for handler in &module.gated_handlers {
+            handlers.push(handler_report(
+                handler,
+                &file,
+                false,
+                exclusion_list,
+                url_types,
+            ));
+        }
+        models.extend(module.models.iter().map(|model| model_report(model, &file)));
+    }
+
+    Inspection {
+        version: FORMAT_VERSION,
+        handlers,
+        models,
+    }
+}
+
+impl Inspection {
+    /// Reads a report written by `inspect --json`.
+    pub fn from_json(json: &str) -> std::io::Result<Inspection> {
+        let inspection: Inspection =
+            serde_json::from_str(json).map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+        if inspection.version != FORMAT_VERSION {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "unsupported inspect format version {}, expected {FORMAT_VERSION}",
+                    inspection.version
+                ),
+            ));
+        }
+        Ok(inspection)
+    }
+
+    /// Serializes the report as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Unable to serialize inspection")
+    }
+
+    /// Summarizes the report with a line per handler and model.
+    ///
+    /// i.e. `GET /threads/<id> -> Thread (src/threads.rs:12 crate::threads::get_thread)`
+    pub fn summary(&self) -> String {
+        let mut lines = vec![];
+        for handler in &self.handlers {
+            let gated = match handler.active {
+                true => "",
+                false => " [cfg]",
+            };
+            lines.push(format!(
+                "{} {} -> {} ({}:{} {}){gated}",
+                handler.method,
+                handler.uri,
+                handler.response.ts_type,
+                handler.file,
+                handler.line,
+                handler.rust_path,
+            ));
+        }
+        for model in &self.models {
+            lines.push(format!(
+                "{} {} ({})",
+                model.kind, model.rust_path, model.file
+            ));
+        }
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,389 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{json, Map, Value};
+use syn::visit_mut::VisitMut;
+use syn::Type;
+
+use crate::generator::options::GeneratorOptions;
+use crate::parser::doc_comments::Docs;
+use crate::parser::handler_params::{handler_params, ParamKind};
+use crate::parser::model::{
+    EnumTagging, ModelField, ModelKind, ModelVariant, RustModel, VariantFields,
+};
+use crate::parser::response_kind::{response_kind, ResponseKind};
+use crate::parser::response_type::response_type;
+use crate::parser::source_module::SourceModule;
+use crate::parser::symbol_table::Substitution;
+use crate::parser::type_args::type_args;
+
+/// The JSON Schema dialect of the generated bundle.
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Returns the `$defs` name of a model instance, i.e. `Paginated_Vec_Comment` for
+/// `Paginated<Vec<Comment>>`.
+fn instance_name(ty: &Type) -> String {
+    match ty {
+        Type::Reference(reference) => instance_name(&reference.elem),
+        Type::Paren(paren) => instance_name(&paren.elem),
+        Type::Group(group) => instance_name(&group.elem),
+        Type::Slice(slice) => format!("Vec_{}", instance_name(&slice.elem)),
+        Type::Array(array) => format!("Vec_{}", instance_name(&array.elem)),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => String::from("Unit"),
+        Type::Tuple(tuple) => {
+            let elems: Vec<String> = tuple.elems.iter().map(instance_name).collect();
+            format!("Tuple_{}", elems.join("_"))
+        }
+        Type::Path(type_path) => match type_path.path.segments.last() {
+            Some(last) => {
+                let mut name = last.ident.to_string();
+                for arg in type_args(last) {
+                    name.push('_');
+                    name.push_str(&instance_name(arg));
+                }
+                name
+            }
+            None => String::from("Value"),
+        },
+        _ => String::from("Value"),
+    }
+}
+
+/// Adds the documentation of an item to its schema as `description` and `deprecated`.
+fn annotate(mut schema: Value, docs: &Docs) -> Value {
+    if let Value::Object(object) = &mut schema {
+        if !docs.lines.is_empty() {
+            object.insert(String::from("description"), json!(docs.lines.join("\n")));
+        }
+        if docs.deprecated.is_some() {
+            object.insert(String::from("deprecated"), json!(true));
+        }
+    }
+    schema
+}
+
+/// Collects the `$defs` of the models reachable from the handlers.
+struct Schemas<'a> {
+    models: HashMap<&'a str, &'a RustModel>,
+    defs: Map<String, Value>,
+}
+
+impl Schemas<'_> {
+    /// Maps a Rust type to its schema, adding the definitions of the models it refers to.
+    ///
+    /// Primitives and standard library containers are mapped like `ts_type` does, i.e. `i128`
+    /// to a string. Types that are neither are unconstrained.
+    fn schema(&mut self, ty: &Type) -> Value {
+        match ty {
+            Type::Reference(reference) => self.schema(&reference.elem),
+            Type::Paren(paren) => self.schema(&paren.elem),
+            Type::Group(group) => self.schema(&group.elem),
+            Type::Slice(slice) => json!({ "type": "array", "items": self.schema(&slice.elem) }),
+            Type::Array(array) => json!({ "type": "array", "items": self.schema(&array.elem) }),
+            Type::Tuple(tuple) if tuple.elems.is_empty() => json!({ "type": "null" }),
+            Type::Tuple(tuple) => {
+                let items: Vec<Value> = tuple.elems.iter().map(|ty| self.schema(ty)).collect();
+                json!({
+                    "type": "array",
+                    "prefixItems": items,
+                    "minItems": items.len(),
+                    "maxItems": items.len(),
+                })
+            }
+            Type::Path(type_path) => {
+                let Some(last) = type_path.path.segments.last() else {
+                    return json!({});
+                };
+                let args = type_args(last);
+
+                match (last.ident.to_string().as_str(), args.as_slice()) {
+                    ("i8" | "i16" | "i32" | "i64" | "isize", _) => json!({ "type": "integer" }),
+                    ("u8" | "u16" | "u32" | "u64" | "usize", _) => {
+                        json!({ "type": "integer", "minimum": 0 })
+                    }
+                    ("f32" | "f64", _) => json!({ "type": "number" }),
+                    ("i128" | "u128", _) => json!({ "type": "string" }),
+                    ("bool", _) => json!({ "type": "boolean" }),
+                    ("String" | "str" | "PathBuf" | "Path", _) => json!({ "type": "string" }),
+                    ("char", _) => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+                    ("Option", [inner]) => {
+                        json!({ "anyOf": [self.schema(inner), { "type": "null" }] })
+                    }
+                    ("Vec" | "VecDeque", [inner]) => {
+                        json!({ "type": "array", "items": self.schema(inner) })
+                    }
+                    ("HashSet" | "BTreeSet", [inner]) => {
+                        json!({ "type": "array", "items": self.schema(inner), "uniqueItems": true })
+                    }
+                    ("HashMap" | "BTreeMap", [_, value]) => {
+                        json!({ "type": "object", "additionalProperties": self.schema(value) })
+                    }
+                    ("Box" | "Arc" | "Rc" | "Cow", [.., inner]) => self.schema(inner),
+                    (ident, args) => match self.models.get(ident).copied() {
+                        Some(model) => {
+                            let args = args.iter().map(|arg| (*arg).clone()).collect();
+                            json!({ "$ref": format!("#/$defs/{}", self.define(model, ty, args)) })
+                        }
+                        None => json!({}),
+                    },
+                }
+            }
+            _ => json!({}),
+        }
+    }
+
+    /// Maps the Rust type of a path segment or query value to its schema, like `url_ts_type`.
+    ///
+    /// Custom URL value types are sent as the string, or the `--url-type` override, callers
+    /// send, so the models they may also be are not reached through them.
+    fn url_schema(&mut self, ty: &Type, url_types: &BTreeMap<String, String>) -> Value {
+        match ty {
+            Type::Reference(reference) => self.url_schema(&reference.elem, url_types),
+            Type::Paren(paren) => self.url_schema(&paren.elem, url_types),
+            Type::Group(group) => self.url_schema(&group.elem, url_types),
+            Type::Path(type_path) => {
+                let Some(last) = type_path.path.segments.last() else {
+                    return self.schema(ty);
+                };
+                let ident = last.ident.to_string();
+                if let Some(ts) = url_types.get(&ident) {
+                    return match ts.as_str() {
+                        "string" => json!({ "type": "string" }),
+                        "number" => json!({ "type": "number" }),
+                        "boolean" => json!({ "type": "boolean" }),
+                        _ => json!({}),
+                    };
+                }
+
+                match (ident.as_str(), type_args(last).as_slice()) {
+                    ("Option", [inner]) => {
+                        json!({ "anyOf": [self.url_schema(inner, url_types), { "type": "null" }] })
+                    }
+                    ("Result", [inner, ..]) => self.url_schema(inner, url_types),
+                    ("Vec", [inner]) => {
+                        json!({ "type": "array", "items": self.url_schema(inner, url_types) })
+                    }
+                    _ => self.schema(ty),
+                }
+            }
+            _ => self.schema(ty),
+        }
+    }
+
+    /// Adds the definition of a model instance unless already present, returning its name.
+    fn define(&mut self, model: &RustModel, ty: &Type, args: Vec<Type>) -> String {
+        let name = instance_name(ty);
+        if self.defs.contains_key(&name) {
+            return name;
+        }
+        // Reserve the name first, so recursive models refer to themselves.
+        self.defs.insert(name.clone(), Value::Null);
+
+        let mut substitution = Substitution {
+            params: &model.generics,
+            args,
+        };
+        let mut schema_of = |schemas: &mut Self, ty: &Type| {
+            let mut ty = ty.clone();
+            substitution.visit_type_mut(&mut ty);
+            schemas.schema(&ty)
+        };
+
+        let schema = match &model.kind {
+            ModelKind::Struct(fields) => self.object(fields, &mut schema_of),
+            ModelKind::Newtype(inner) => schema_of(self, inner),
+            ModelKind::NumericEnum(variants) => {
+                let values: Vec<i64> = variants
+                    .iter()
+                    .map(|v| v.discriminant.unwrap_or_default())
+                    .collect();
+                json!({ "type": "integer", "enum": values })
+            }
+            ModelKind::Enum(variants, _) if variants.is_empty() => json!({ "not": {} }),
+            ModelKind::Enum(variants, EnumTagging::External)
+                if variants
+                    .iter()
+                    .all(|v| matches!(v.fields, VariantFields::Unit)) =>
+            {
+                let values: Vec<&str> = variants.iter().map(|v| v.wire_name.as_str()).collect();
+                json!({ "type": "string", "enum": values })
+            }
+            ModelKind::Enum(variants, tagging) => {
+                let variants: Vec<Value> = variants
+                    .iter()
+                    .map(|variant| self.variant(variant, tagging, &mut schema_of))
+                    .collect();
+                match tagging {
+                    // serde takes the first untagged variant that matches.
+                    EnumTagging::Untagged => json!({ "anyOf": variants }),
+                    _ => json!({ "oneOf": variants }),
+                }
+            }
+        };
+
+        self.defs
+            .insert(name.clone(), annotate(schema, &model.docs));
+        name
+    }
+
+    /// Renders the fields of a struct (or struct variant) as an object schema.
+    fn object(
+        &mut self,
+        fields: &[ModelField],
+        schema_of: &mut impl FnMut(&mut Self, &Type) -> Value,
+    ) -> Value {
+        let mut properties = Map::new();
+        let mut required = vec![];
+        for field in fields {
+            let schema = schema_of(self, &field.ty);
+            properties.insert(field.wire_name.clone(), annotate(schema, &field.docs));
+            if !field.optional {
+                required.push(field.wire_name.clone());
+            }
+        }
+        json!({ "type": "object", "properties": properties, "required": required })
+    }
+
+    /// Renders an enum variant in the representation of its enum.
+    ///
+    /// i.e. `Open` -> `{ "const": "Open" }` externally tagged, `Closed(Reason)` -> an object with
+    /// a single `Closed` property externally tagged, `Reason` along with a `type` property with
+    /// `tag = "type"`, a `t` and a `c` property with `tag = "t", content = "c"` and `Reason` alone
+    /// untagged
+    fn variant(
+        &mut self,
+        variant: &ModelVariant,
+        tagging: &EnumTagging,
+        schema_of: &mut impl FnMut(&mut Self, &Type) -> Value,
+    ) -> Value {
+        let content = match &variant.fields {
+            VariantFields::Unit => None,
+            VariantFields::Tuple(types) if types.len() == 1 => Some(schema_of(self, &types[0])),
+            VariantFields::Tuple(types) => {
+                let items: Vec<Value> = types.iter().map(|ty| schema_of(self, ty)).collect();
+                Some(json!({
+                    "type": "array",
+                    "prefixItems": items,
+                    "minItems": items.len(),
+                    "maxItems": items.len(),
+                }))
+            }
+            VariantFields::Named(fields) => Some(self.object(fields, schema_of)),
+        };
+        let wire_name = json!({ "const": variant.wire_name });
+
+        let schema = match (tagging, content) {
+            (EnumTagging::External, None) => wire_name,
+            (EnumTagging::External, Some(content)) => json!({
+                "type": "object",
+                "properties": { variant.wire_name.clone(): content },
+                "required": [variant.wire_name],
+                "additionalProperties": false,
+            }),
+            (EnumTagging::Internal { tag }, None) => json!({
+                "type": "object",
+                "properties": { tag.clone(): wire_name },
+                "required": [tag],
+            }),
+            (EnumTagging::Internal { tag }, Some(mut content)) => match &variant.fields {
+                // The fields sit next to the tag.
+                VariantFields::Named(_) => {
+                    content["properties"][tag] = wire_name;
+                    if let Some(required) = content["required"].as_array_mut() {
+                        required.insert(0, json!(tag));
+                    }
+                    content
+                }
+                // serde can't serialize tuple variants with an internal tag.
+                VariantFields::Tuple(types) if types.len() > 1 => json!({ "not": {} }),
+                _ => json!({
+                    "allOf": [
+                        {
+                            "type": "object",
+                            "properties": { tag.clone(): wire_name },
+                            "required": [tag],
+                        },
+                        content,
+                    ],
+                }),
+            },
+            (EnumTagging::Adjacent { tag, .. }, None) => json!({
+                "type": "object",
+                "properties": { tag.clone(): wire_name },
+                "required": [tag],
+                "additionalProperties": false,
+            }),
+            (EnumTagging::Adjacent { tag, content: key }, Some(content)) => json!({
+                "type": "object",
+                "properties": { tag.clone(): wire_name, key.clone(): content },
+                "required": [tag, key],
+                "additionalProperties": false,
+            }),
+            (EnumTagging::Untagged, None) => json!({ "type": "null" }),
+            (EnumTagging::Untagged, Some(content)) => content,
+        };
+        annotate(schema, &variant.docs)
+    }
+}
+
+/// Generates a JSON Schema (draft 2020-12) bundle with a `$defs` entry per model reachable from
+/// the handlers.
+///
+/// Models are reached through request bodies, query and path values, JSON responses and the
+/// event payloads given with `#[rocket_ts(event = "...")]`. Fields and variants are named and
+/// made optional by the same serde attributes as in the TypeScript declarations. Generic models
+/// have a definition per instance, i.e. `Paginated_Thread` for `Paginated<Thread>`.
+///
+/// Types are overridden as in the TypeScript declarations: custom URL value types don't reach
+/// models, see `url_schema`, and a `#[rocket_ts(returns = "...")]` response reaches the model it
+/// names, if any, instead of the Rust return type.
+///
+/// # Arguments
+///
+/// * `modules` - The handlers and models extracted from each source file.
+/// * `options` - The generator options, for the excluded parameter and URL value types.
+pub fn json_schema(modules: &[SourceModule], options: &GeneratorOptions) -> String {
+    let mut schemas = Schemas {
+        models: HashMap::new(),
+        defs: Map::new(),
+    };
+    for model in modules.iter().flat_map(|m| &m.models) {
+        schemas.models.entry(model.name.as_str()).or_insert(model);
+    }
+
+    for handler in modules.iter().flat_map(|m| &m.handlers) {
+        for param in handler_params(handler, &options.exclusion_list, &options.url_types) {
+            match param.kind {
+                ParamKind::Guard => {}
+                ParamKind::Path | ParamKind::Segments | ParamKind::Query => {
+                    schemas.url_schema(&param.ty, &options.url_types);
+                }
+                _ => {
+                    schemas.schema(&param.ty);
+                }
+            }
+        }
+
+        if let Some(returns) = &handler.returns {
+            // Only overrides naming a Rust type, i.e. a model, can be followed.
+            if let Ok(ty) = syn::parse_str::<Type>(returns) {
+                schemas.schema(&ty);
+            }
+            continue;
+        }
+
+        match response_kind(handler) {
+            ResponseKind::Json => {
+                if let Some(ty) = response_type(&handler.return_type) {
+                    schemas.schema(&ty);
+                }
+            }
+            ResponseKind::EventStream(Some(event)) => {
+                schemas.schema(&event);
+            }
+            _ => {}
+        }
+    }
+
+    let bundle = json!({ "$schema": DRAFT, "$defs": schemas.defs });
+    serde_json::to_string_pretty(&bundle).expect("Unable to serialize JSON Schema")
+}
@@ -0,0 +1,14 @@
+pub mod api_diff;
+pub mod api_interface;
+pub mod axios_client;
+pub mod fetch_client;
+pub mod handler_members;
+pub mod inspection;
+pub mod json_schema;
+pub mod model_declarations;
+pub mod module_members;
+pub mod msw_handlers;
+pub mod options;
+pub mod query_hooks;
+pub mod tsdoc;
+pub mod wire_conversion;
@@ -0,0 +1,208 @@
+use crate::generator::options::EnumStyle;
+use crate::generator::options::GeneratorOptions;
+use crate::generator::tsdoc::tsdoc;
+use crate::parser::model::{
+    EnumTagging, ModelField, ModelKind, ModelVariant, RustModel, VariantFields,
+};
+use crate::parser::ts_type::ts_type;
+
+/// Returns the name a field is emitted under.
+///
+/// Its serde wire name, unless a naming convention is configured. The generated client then maps
+/// between both.
+pub fn field_name(field: &ModelField, options: &GeneratorOptions) -> String {
+    match options.naming {
+        Some(naming) => naming.apply(&field.name),
+        None => field.wire_name.clone(),
+    }
+}
+
+/// Quotes a property name unless it is a valid TypeScript identifier.
+///
+/// i.e. "mask" -> "mask", "ticket-mask" -> "\"ticket-mask\""
+pub fn property_key(name: &str) -> String {
+    let is_ident = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+
+    if is_ident {
+        name.to_string()
+    } else {
+        format!("{name:?}")
+    }
+}
+
+/// Renders the fields of a struct (or struct variant) as TypeScript object members.
+fn field_members(fields: &[ModelField], options: &GeneratorOptions, indent: &str) -> String {
+    let mut ts = String::new();
+    for field in fields {
+        ts.push_str(&tsdoc(&field.docs, indent));
+        ts.push_str(&format!(
+            "{indent}{}{}: {};\n",
+            property_key(&field_name(field, options)),
+            if field.optional { "?" } else { "" },
+            ts_type(&field.ty)
+        ));
+    }
+    ts
+}
+
+/// Renders the data carried by an enum variant, None for unit variants.
+///
+/// i.e. `Closed(Reason)` -> `Reason`, `Moved(u32, u32)` -> `[number, number]`
+fn variant_data(
+    variant: &ModelVariant,
+    options: &GeneratorOptions,
+    indent: &str,
+) -> Option<String> {
+    match &variant.fields {
+        VariantFields::Unit => None,
+        VariantFields::Tuple(types) if types.len() == 1 => Some(ts_type(&types[0])),
+        VariantFields::Tuple(types) => {
+            let types: Vec<String> = types.iter().map(ts_type).collect();
+            Some(format!("[{}]", types.join(", ")))
+        }
+        VariantFields::Named(fields) => Some(format!(
+            "{{\n{}{indent}}}",
+            field_members(fields, options, &format!("{indent}\t"))
+        )),
+    }
+}
+
+/// Renders an enum variant in the representation of its enum.
+///
+/// i.e. `Closed(Reason)` -> `{ Closed: Reason }` externally tagged, `{ type: "Closed" } & Reason`
+/// with `tag = "type"`, `{ t: "Closed"; c: Reason }` with `tag = "t", content = "c"` and
+/// `Reason` untagged
+fn variant_type(
+    variant: &ModelVariant,
+    tagging: &EnumTagging,
+    options: &GeneratorOptions,
+) -> String {
+    let wire_name = format!("{:?}", variant.wire_name);
+    match tagging {
+        EnumTagging::External => match variant_data(variant, options, "\t") {
+            None => wire_name,
+            Some(data) => format!("{{ {}: {data} }}", property_key(&variant.wire_name)),
+        },
+        EnumTagging::Internal { tag } => {
+            let tag = property_key(tag);
+            match &variant.fields {
+                VariantFields::Unit => format!("{{ {tag}: {wire_name} }}"),
+                VariantFields::Tuple(types) if types.len() == 1 => {
+                    format!("{{ {tag}: {wire_name} }} & {}", ts_type(&types[0]))
+                }
+                // serde can't serialize tuple variants with an internal tag.
+                VariantFields::Tuple(_) => String::from("never"),
+                VariantFields::Named(fields) => format!(
+                    "{{\n\t\t{tag}: {wire_name};\n{}\t}}",
+                    field_members(fields, options, "\t\t")
+                ),
+            }
+        }
+        EnumTagging::Adjacent { tag, content } => match variant_data(variant, options, "\t") {
+            None => format!("{{ {}: {wire_name} }}", property_key(tag)),
+            Some(data) => format!(
+                "{{ {}: {wire_name}; {}: {data} }}",
+                property_key(tag),
+                property_key(content)
+            ),
+        },
+        EnumTagging::Untagged => {
+            variant_data(variant, options, "\t").unwrap_or_else(|| String::from("null"))
+        }
+    }
+}
+
+/// Renders a TypeScript `enum` declaration.
+///
+/// # Arguments
+///
+/// * `name` - The name of the enum.
+/// * `variants` - The variants, for their documentation.
+/// * `members` - The name and value of each member, i.e. `("High", "2")`.
+fn enum_declaration(name: &str, variants: &[ModelVariant], members: &[(String, String)]) -> String {
+    let mut ts = format!("export enum {name} {{\n");
+    for (variant, (member, value)) in variants.iter().zip(members) {
+        ts.push_str(&tsdoc(&variant.docs, "\t"));
+        ts.push_str(&format!("\t{member} = {value},\n"));
+    }
+    ts.push_str("}\n");
+    ts
+}
+
+/// Generates TypeScript declarations for the serializable models found in the input.
+///
+/// Structs become interfaces, newtype structs become type aliases of their inner type and
+/// enums become unions of their variants in serde's representation of the enum, or `enum`
+/// declarations for externally tagged unit-only enums with `EnumStyle::Enum` and `serde_repr`
+/// enums. Type parameters are kept, i.e. `Paginated<T>`.
+/// Documentation is carried over as TSDoc.
+pub fn model_declarations(models: &[&RustModel], options: &GeneratorOptions) -> String {
+    let mut ts = String::new();
+
+    for model in models {
+        ts.push('\n');
+        ts.push_str(&tsdoc(&model.docs, ""));
+        match &model.kind {
+            ModelKind::Struct(fields) => {
+                ts.push_str(&format!("export interface {} {{\n", model.generic_name()));
+                ts.push_str(&field_members(fields, options, "\t"));
+                ts.push_str("}\n");
+            }
+            ModelKind::Newtype(inner) => {
+                ts.push_str(&format!(
+                    "export type {} = {};\n",
+                    model.generic_name(),
+                    ts_type(inner)
+                ));
+            }
+            ModelKind::NumericEnum(variants) => {
+                let members: Vec<(String, String)> = variants
+                    .iter()
+                    .map(|v| {
+                        (
+                            v.name.clone(),
+                            v.discriminant.unwrap_or_default().to_string(),
+                        )
+                    })
+                    .collect();
+                ts.push_str(&enum_declaration(&model.name, variants, &members));
+            }
+            ModelKind::Enum(variants, EnumTagging::External)
+                if options.enum_style == EnumStyle::Enum
+                    && !variants.is_empty()
+                    && variants
+                        .iter()
+                        .all(|v| matches!(v.fields, VariantFields::Unit)) =>
+            {
+                let members: Vec<(String, String)> = variants
+                    .iter()
+                    .map(|v| (v.name.clone(), format!("{:?}", v.wire_name)))
+                    .collect();
+                ts.push_str(&enum_declaration(&model.name, variants, &members));
+            }
+            ModelKind::Enum(variants, _) if variants.is_empty() => {
+                ts.push_str(&format!("export type {} = never;\n", model.generic_name()));
+            }
+            ModelKind::Enum(variants, tagging) => {
+                ts.push_str(&format!("export type {} =\n", model.generic_name()));
+                for variant in variants {
+                    ts.push_str(&tsdoc(&variant.docs, "\t"));
+                    ts.push_str(&format!(
+                        "\t| {}\n",
+                        variant_type(variant, tagging, options)
+                    ));
+                }
+                ts.pop();
+                ts.push_str(";\n");
+            }
+        }
+    }
+
+    ts
+}
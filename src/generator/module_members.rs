@@ -0,0 +1,37 @@
+use crate::generator::api_interface::Layout;
+use crate::parser::source_module::SourceModule;
+use crate::parser::visitor::RocketReqHandler;
+
+/// Renders the members of an object literal returned by a generated factory, i.e.
+/// `createClient`, arranged like the root interface.
+///
+/// With the flat layout, the members of all modules follow each other, grouped by a file-name
+/// comment. Otherwise, each module gets a nested object named after it.
+///
+/// # Arguments
+///
+/// * `modules` - The handlers extracted from each source file.
+/// * `layout` - How the handlers are arranged.
+/// * `member` - Renders the member of a handler in its module at the given indentation.
+pub fn module_members(
+    modules: &[SourceModule],
+    layout: Layout,
+    mut member: impl FnMut(&SourceModule, &RocketReqHandler, &str) -> String,
+) -> String {
+    let mut ts = String::new();
+    for module in modules {
+        if layout == Layout::Flat {
+            ts.push_str(&format!("\t\t// {}\n", module.file_name));
+            for handler in &module.handlers {
+                ts.push_str(&member(module, handler, "\t\t"));
+            }
+        } else {
+            ts.push_str(&format!("\t\t{}: {{\n", module.name));
+            for handler in &module.handlers {
+                ts.push_str(&member(module, handler, "\t\t\t"));
+            }
+            ts.push_str("\t\t},\n");
+        }
+    }
+    ts
+}
@@ -0,0 +1,172 @@
+use std::collections::BTreeSet;
+
+use crate::generator::api_interface::{emitted_models, header};
+use crate::generator::fetch_client::client_response_kind;
+use crate::generator::handler_members::{handler_comment, handler_name, handler_return_type};
+use crate::generator::model_declarations::model_declarations;
+use crate::generator::module_members::module_members;
+use crate::generator::options::GeneratorOptions;
+use crate::generator::wire_conversion::{converted_models, converter, wire_converters, Direction};
+use crate::parser::handler_params::{handler_params, BodyFormat, ParamKind};
+use crate::parser::response_kind::ResponseKind;
+use crate::parser::response_type::response_type;
+use crate::parser::source_module::SourceModule;
+use crate::parser::visitor::RocketReqHandler;
+
+/// The MSW imports shared by all generated mocks.
+const RUNTIME: &str = r#"import { http } from "msw";
+import type { DefaultBodyType, HttpResponseResolver, PathParams } from "msw";
+"#;
+
+/// Helper converting mocked responses to their wire names, emitted if any model is converted.
+const ENCODE_RUNTIME: &str = r#"
+import { HttpResponse } from "msw";
+
+/** Re-encodes the JSON body of the mocked responses, keeping their status and headers. */
+function encoded<P extends PathParams, B extends DefaultBodyType, R extends DefaultBodyType>(
+	resolver: HttpResponseResolver<P, B, R>,
+	encode: (v: R) => unknown,
+): HttpResponseResolver<P, B, DefaultBodyType> {
+	return async (info) => {
+		const response = await resolver(info);
+		if (!(response instanceof Response) || !response.headers.get("Content-Type")?.includes("json")) {
+			return response;
+		}
+		return HttpResponse.json(encode(await response.json()) as DefaultBodyType, response);
+	};
+}
+"#;
+
+/// Converts a Rocket route to an MSW path pattern, leaving out the query.
+///
+/// i.e. "/thread/<kid>/files/<path..>?<page>" -> "/thread/:kid/files/:path*"
+pub fn msw_path(path: &str) -> String {
+    let path = path.split('?').next().unwrap_or_default();
+    let segments: Vec<String> = path
+        .split('/')
+        .map(
+            |segment| match segment.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+                Some(name) => match name.strip_suffix("..") {
+                    Some(name) => format!(":{name}*"),
+                    None => format!(":{name}"),
+                },
+                None => segment.to_string(),
+            },
+        )
+        .collect();
+    segments.join("/")
+}
+
+/// Renders the type of the path parameters MSW matches, i.e. `{ kid: string; path: string[] }`.
+fn params_type(path: &str) -> String {
+    let path = path.split('?').next().unwrap_or_default();
+    let params: Vec<String> = path
+        .split('/')
+        .filter_map(|segment| segment.strip_prefix('<')?.strip_suffix('>'))
+        .map(|name| match name.strip_suffix("..") {
+            Some(name) => format!("{name}: string[]"),
+            None => format!("{name}: string"),
+        })
+        .collect();
+
+    match params.is_empty() {
+        true => String::from("PathParams"),
+        false => format!("{{ {} }}", params.join("; ")),
+    }
+}
+
+/// Renders a handler as a function creating its MSW request handler from a resolver.
+fn mock(
+    handler: &RocketReqHandler,
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+    indent: &str,
+) -> String {
+    let name = handler_name(handler, options);
+    let kind = client_response_kind(handler);
+    if let ResponseKind::WebSocket = kind {
+        return format!("{indent}// {name}: websocket handlers have no HTTP mock\n");
+    }
+
+    let body = handler_params(handler, &options.exclusion_list, &options.url_types)
+        .into_iter()
+        .find(|p| p.kind == ParamKind::Body);
+    let request_body = match body {
+        Some(body)
+            if body.body_format == Some(BodyFormat::MsgPack)
+                || converter(&body.ty, Direction::Decode, converted).is_some() =>
+        {
+            String::from("DefaultBodyType")
+        }
+        Some(body) => body.ts_type,
+        None => String::from("never"),
+    };
+
+    let response_body = match kind {
+        ResponseKind::Text | ResponseKind::Html => String::from("string"),
+        ResponseKind::Binary => String::from("ArrayBuffer | Blob"),
+        ResponseKind::Redirect | ResponseKind::Empty => String::from("null"),
+        ResponseKind::EventStream(_) | ResponseKind::TextStream | ResponseKind::ByteStream => {
+            String::from("DefaultBodyType")
+        }
+        _ => handler_return_type(handler),
+    };
+    let encode = match kind {
+        ResponseKind::Json => response_type(&handler.return_type)
+            .filter(|_| handler.returns.is_none())
+            .and_then(|ty| converter(&ty, Direction::Encode, converted)),
+        _ => None,
+    };
+    let resolver = match encode {
+        Some(f) => format!("encoded(resolver, {f})"),
+        None => String::from("resolver"),
+    };
+
+    let mut ts = handler_comment(handler, indent);
+    ts.push_str(&format!(
+        "{indent}{name}: (resolver: HttpResponseResolver<{}, {request_body}, {response_body}>) =>\n",
+        params_type(&handler.path)
+    ));
+    ts.push_str(&format!(
+        "{indent}\thttp.{}(`${{baseUrl}}{}`, {resolver}),\n",
+        handler.method,
+        msw_path(&handler.path)
+    ));
+    ts
+}
+
+/// Generates [MSW](https://mswjs.io) request handlers mocking the routes, for frontend tests.
+///
+/// `createMocks(baseUrl)` returns an object arranged like the root interface, with a function
+/// per handler taking a resolver and returning the request handler for its route. Rocket's
+/// `<param>` and `<path..>` segments become `:param` and `:path*`. Resolvers are typed with the
+/// path parameters, the request body and the response type, so `HttpResponse.json(...)` only
+/// accepts the endpoint's response. Where a naming convention renamed model fields, responses are
+/// re-encoded to their wire names. Models are declared in the same file with `--models`.
+///
+/// # Arguments
+///
+/// * `modules` - The handlers and models extracted from each source file.
+/// * `options` - The generator options.
+pub fn msw_handlers(modules: &[SourceModule], options: &GeneratorOptions) -> String {
+    let models = emitted_models(modules, options);
+    let converted = converted_models(&models, options);
+
+    let mut ts = header();
+    ts.push_str(RUNTIME);
+    if !converted.is_empty() {
+        ts.push_str(ENCODE_RUNTIME);
+    }
+
+    ts.push_str("\nexport function createMocks(baseUrl = \"\") {\n\treturn {\n");
+    ts.push_str(&module_members(
+        modules,
+        options.layout,
+        |_, handler, indent| mock(handler, options, &converted, indent),
+    ));
+    ts.push_str("\t};\n}\n\nexport type Mocks = ReturnType<typeof createMocks>;\n");
+
+    ts.push_str(&model_declarations(&models, options));
+    ts.push_str(&wire_converters(&models, options, &converted));
+    ts
+}
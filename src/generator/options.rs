@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use clap::ValueEnum;
+
+use crate::generator::api_interface::Layout;
+use crate::parser::case_conversion::Naming;
+
+/// The HTTP client library a generated client is built on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ClientTarget {
+    /// The standard `fetch` API.
+    Fetch,
+    /// An `AxiosInstance`, so its interceptors apply.
+    Axios,
+}
+
+/// The data-fetching library hooks are generated for, layered on the generated client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HooksTarget {
+    /// TanStack Query's React hooks, `useQuery` and `useMutation`.
+    ReactQuery,
+}
+
+/// What `generate` emits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// TypeScript declarations of the handlers, and models and a client if requested.
+    #[value(name = "typescript")]
+    TypeScript,
+    /// A JSON Schema bundle of the models reachable from the handlers.
+    JsonSchema,
+    /// MSW request handlers mocking the routes, for frontend tests.
+    Msw,
+}
+
+/// How unit-only enums are declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EnumStyle {
+    /// A union of string literals, e.g. `"Open" | "Closed"`.
+    Union,
+    /// A TypeScript `enum` declaration, e.g. `enum Status { Open = "Open", Closed = "Closed" }`.
+    Enum,
+}
+
+/// Options shared by all generators.
+pub struct GeneratorOptions {
+    /// The name of the exported root interface.
+    pub interface_name: String,
+    /// How handlers are grouped by source module.
+    pub layout: Layout,
+    /// Parameter types to leave out of the signatures (e.g., Request Guards).
+    pub exclusion_list: Vec<String>,
+    /// The TypeScript types of custom path segment and query value types by Rust type name, i.e.
+    /// `string` for the types implementing `FromParam`, unless overridden with `--url-type`.
+    pub url_types: BTreeMap<String, String>,
+    /// Whether to emit declarations for the models.
+    pub models: bool,
+    /// How unit-only enum models are declared.
+    pub enum_style: EnumStyle,
+    /// The naming convention of emitted identifiers. Verbatim if None.
+    pub naming: Option<Naming>,
+    /// The client to generate next to the interface, if any.
+    pub client: Option<ClientTarget>,
+    /// The hooks to generate on top of the client, if any.
+    pub hooks: Option<HooksTarget>,
+    /// The name of the interface holding handlers compiled out by `#[cfg(...)]`. They are
+    /// left out entirely if None.
+    pub gated_interface: Option<String>,
+}
+
+impl GeneratorOptions {
+    /// Converts a Rust identifier to the configured naming convention.
+    pub fn name(&self, ident: &str) -> String {
+        match self.naming {
+            Some(naming) => naming.apply(ident),
+            None => ident.to_string(),
+        }
+    }
+}
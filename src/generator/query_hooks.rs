@@ -0,0 +1,168 @@
+use crate::generator::api_interface::Layout;
+use crate::generator::fetch_client::{client_data_type, client_params, client_signature};
+use crate::generator::handler_members::{handler_comment, handler_name};
+use crate::generator::module_members::module_members;
+use crate::generator::options::GeneratorOptions;
+use crate::generator::tsdoc::tsdoc;
+use crate::parser::case_conversion::Naming;
+use crate::parser::handler_params::{HandlerParam, ParamKind};
+use crate::parser::response_kind::{response_kind, ResponseKind};
+use crate::parser::source_module::SourceModule;
+use crate::parser::visitor::RocketReqHandler;
+
+/// The TanStack Query imports and option types shared by all generated hooks.
+const RUNTIME: &str = r#"
+import { useMutation, useQuery } from "@tanstack/react-query";
+import type { UseMutationOptions, UseQueryOptions } from "@tanstack/react-query";
+
+type QueryOptions<T> = Omit<UseQueryOptions<T, Error, T>, "queryKey" | "queryFn">;
+type MutationOptions<T, V> = Omit<UseMutationOptions<T, Error, V>, "mutationFn">;
+"#;
+
+/// Renders the query key of a handler from its route template and parameters.
+///
+/// Static path segments are kept as strings, path parameters are inserted in their place and
+/// query parameters are collected into a trailing object.
+///
+/// i.e. "/thread/<kid>/comments?<page>" -> `["thread", kid, "comments", { "page": page }]`
+fn query_key(
+    handler: &RocketReqHandler,
+    params: &[HandlerParam],
+    options: &GeneratorOptions,
+) -> String {
+    let path = handler.path.split('?').next().unwrap_or_default();
+
+    let mut elements = vec![];
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        match segment.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+            Some(name) => elements.push(options.name(name.trim_end_matches(".."))),
+            None => elements.push(format!("{segment:?}")),
+        }
+    }
+
+    let mut query = vec![];
+    for param in params {
+        let name = options.name(&param.name);
+        match param.kind {
+            ParamKind::Query => query.push(format!("{:?}: {name}", param.name)),
+            ParamKind::QueryFields => query.push(format!("...{name}")),
+            ParamKind::Body => elements.push(name),
+            _ => {}
+        }
+    }
+    if !query.is_empty() {
+        elements.push(format!("{{ {} }}", query.join(", ")));
+    }
+
+    format!("[{}] as const", elements.join(", "))
+}
+
+/// Renders the hook of a handler: a query hook and its key for GET handlers, a mutation hook for
+/// the methods changing state.
+fn hook(
+    handler: &RocketReqHandler,
+    options: &GeneratorOptions,
+    client_path: &str,
+    indent: &str,
+) -> String {
+    let name = handler_name(handler, options);
+    if matches!(
+        response_kind(handler),
+        ResponseKind::EventStream(_)
+            | ResponseKind::TextStream
+            | ResponseKind::ByteStream
+            | ResponseKind::WebSocket
+    ) {
+        return format!("{indent}// {name}: streaming responses have no hook\n");
+    }
+
+    let hook_name = format!("use{}", Naming::Pascal.apply(&handler.name));
+    let params = client_params(handler, options);
+    let names: Vec<String> = params.iter().map(|p| options.name(&p.name)).collect();
+    let call = format!("{client_path}.{name}({})", names.join(", "));
+    let data = client_data_type(handler);
+
+    let mut ts = handler_comment(handler, indent);
+    ts.push_str(&tsdoc(&handler.docs, indent));
+    match handler.method.as_str() {
+        "get" => {
+            let signature = client_signature(&params, options);
+            let key = query_key(handler, &params, options);
+            let separator = if signature.is_empty() { "" } else { ", " };
+            ts.push_str(&format!(
+                "{indent}{}QueryKey: ({signature}) => {key},\n",
+                Naming::Camel.apply(&handler.name)
+            ));
+            ts.push_str(&format!(
+                "{indent}{hook_name}: ({signature}{separator}__options?: QueryOptions<{data}>) =>\n"
+            ));
+            ts.push_str(&format!(
+                "{indent}\tuseQuery({{ queryKey: {key}, queryFn: () => {call}, ...__options }}),\n"
+            ));
+        }
+        "post" | "put" | "patch" | "delete" => {
+            let (variables, pattern) = match params.is_empty() {
+                true => (String::from("void"), String::new()),
+                false => {
+                    let fields: Vec<String> = params
+                        .iter()
+                        .zip(&names)
+                        .map(|(param, name)| match param.optional {
+                            true => format!("{name}?: {}", param.ts_type),
+                            false => format!("{name}: {}", param.ts_type),
+                        })
+                        .collect();
+                    let variables = format!("{{ {} }}", fields.join("; "));
+                    let pattern = format!("{{ {} }}: {variables}", names.join(", "));
+                    (variables, pattern)
+                }
+            };
+            ts.push_str(&format!(
+                "{indent}{hook_name}: (__options?: MutationOptions<{data}, {variables}>) =>\n"
+            ));
+            ts.push_str(&format!(
+                "{indent}\tuseMutation({{ mutationFn: ({pattern}) => {call}, ...__options }}),\n"
+            ));
+        }
+        method => {
+            return format!(
+                "{indent}// {name}: {} handlers have no hook\n",
+                method.to_uppercase()
+            );
+        }
+    }
+    ts
+}
+
+/// Generates TanStack Query hooks layered on the generated client.
+///
+/// `createHooks(client)` returns an object arranged like the root interface, with a
+/// `use<Handler>` query hook and a `<handler>QueryKey` function per GET handler, and a
+/// `use<Handler>` mutation hook per POST, PUT, PATCH and DELETE handler taking its parameters as
+/// one variables object. Query keys follow the route template, see `query_key`. Every hook
+/// accepts the options of `useQuery` or `useMutation` besides the key and function. The client
+/// and options are named with a `__` prefix, so handler parameters can't collide with them.
+///
+/// # Arguments
+///
+/// * `modules` - The handlers extracted from each source file.
+/// * `options` - The generator options.
+pub fn query_hooks(modules: &[SourceModule], options: &GeneratorOptions) -> String {
+    let mut ts = String::from(RUNTIME);
+
+    ts.push_str("\nexport function createHooks(__client: Client) {\n\treturn {\n");
+    ts.push_str(&module_members(
+        modules,
+        options.layout,
+        |module, handler, indent| {
+            let client_path = match options.layout {
+                Layout::Flat => String::from("__client"),
+                _ => format!("__client.{}", module.name),
+            };
+            hook(handler, options, &client_path, indent)
+        },
+    ));
+    ts.push_str("\t};\n}\n\nexport type Hooks = ReturnType<typeof createHooks>;\n");
+
+    ts
+}
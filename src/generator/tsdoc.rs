@@ -0,0 +1,38 @@
+use crate::parser::doc_comments::Docs;
+
+/// Renders documentation as a TSDoc comment, so editor hovers on the frontend show it.
+///
+/// # Arguments
+///
+/// * `docs` - The documentation carried over from the Rust item.
+/// * `indent` - The indentation to prefix each line with.
+///
+/// # Returns
+///
+/// The `/** ... */` comment including a trailing newline, or an empty String if there is nothing
+/// to document.
+pub fn tsdoc(docs: &Docs, indent: &str) -> String {
+    let mut lines: Vec<String> = docs.lines.iter().map(|l| l.replace("*/", "*\\/")).collect();
+
+    if let Some(note) = &docs.deprecated {
+        let note = note.replace("*/", "*\\/");
+        lines.push(format!("@deprecated {note}").trim_end().to_string());
+    }
+
+    match lines.as_slice() {
+        [] => String::new(),
+        [line] => format!("{indent}/** {line} */\n"),
+        _ => {
+            let mut ts = format!("{indent}/**\n");
+            for line in lines {
+                if line.is_empty() {
+                    ts.push_str(&format!("{indent} *\n"));
+                } else {
+                    ts.push_str(&format!("{indent} * {line}\n"));
+                }
+            }
+            ts.push_str(&format!("{indent} */\n"));
+            ts
+        }
+    }
+}
@@ -0,0 +1,431 @@
+use std::collections::BTreeSet;
+
+use syn::Type;
+
+use crate::generator::model_declarations::{field_name, property_key};
+use crate::generator::options::GeneratorOptions;
+use crate::parser::model::{
+    EnumTagging, ModelField, ModelKind, ModelVariant, RustModel, VariantFields,
+};
+use crate::parser::type_args::type_args;
+
+/// Which way a value crosses the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// From JSON received from Rocket to the generated TypeScript types.
+    Decode,
+    /// From the generated TypeScript types to JSON sent to Rocket.
+    Encode,
+}
+
+impl Direction {
+    fn prefix(&self) -> &'static str {
+        match self {
+            Direction::Decode => "decode",
+            Direction::Encode => "encode",
+        }
+    }
+}
+
+/// Renders a call of a converter, wrapping arrow functions in parentheses.
+pub fn call(converter: &str, expr: &str) -> String {
+    if converter.contains(' ') {
+        format!("({converter})({expr})")
+    } else {
+        format!("{converter}({expr})")
+    }
+}
+
+/// Renders `expr` passed through an optional converter.
+fn apply(converter: &Option<String>, expr: &str) -> String {
+    match converter {
+        Some(converter) => call(converter, expr),
+        None => expr.to_string(),
+    }
+}
+
+/// Renders a property access, i.e. `v.kid` or `v["ticket-mask"]`.
+fn access(object: &str, name: &str) -> String {
+    let key = property_key(name);
+    if key.starts_with('"') {
+        format!("{object}[{key}]")
+    } else {
+        format!("{object}.{key}")
+    }
+}
+
+/// Provides a TypeScript function converting a value of a Rust type across the wire.
+///
+/// i.e. Option<Vec<Thread>> -> "(x: any) => x == null ? x : x.map(decodeThread)"
+///
+/// Converters of generic models take a converter per type argument, i.e. Paginated<Thread> ->
+/// "(x: any) => decodePaginated(x, decodeThread)".
+///
+/// # Returns
+///
+/// None if the value is the same on both sides, so no conversion is needed.
+pub fn converter(ty: &Type, direction: Direction, converted: &BTreeSet<String>) -> Option<String> {
+    match ty {
+        Type::Reference(reference) => converter(&reference.elem, direction, converted),
+        Type::Paren(paren) => converter(&paren.elem, direction, converted),
+        Type::Group(group) => converter(&group.elem, direction, converted),
+        Type::Slice(slice) => converter(&slice.elem, direction, converted)
+            .map(|f| format!("(x: any[]) => x.map({f})")),
+        Type::Array(array) => converter(&array.elem, direction, converted)
+            .map(|f| format!("(x: any[]) => x.map({f})")),
+        Type::Tuple(tuple) => {
+            let elems: Vec<Option<String>> = tuple
+                .elems
+                .iter()
+                .map(|elem| converter(elem, direction, converted))
+                .collect();
+            if elems.iter().all(Option::is_none) {
+                return None;
+            }
+            let elems: Vec<String> = elems
+                .iter()
+                .enumerate()
+                .map(|(i, f)| apply(f, &format!("x[{i}]")))
+                .collect();
+            Some(format!("(x: any[]) => [{}]", elems.join(", ")))
+        }
+        Type::Path(type_path) => {
+            let last = type_path.path.segments.last()?;
+            let args = type_args(last);
+
+            match (last.ident.to_string().as_str(), args.as_slice()) {
+                ("Option", [inner]) => converter(inner, direction, converted)
+                    .map(|f| format!("(x: any) => x == null ? x : {}", call(&f, "x"))),
+                ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [inner]) => {
+                    converter(inner, direction, converted)
+                        .map(|f| format!("(x: any[]) => x.map({f})"))
+                }
+                ("HashMap" | "BTreeMap", [_, value]) => {
+                    converter(value, direction, converted).map(|f| {
+                        format!(
+                            "(x: any) => Object.fromEntries(Object.entries(x).map(([k, y]) => [k, {}]))",
+                            call(&f, "y")
+                        )
+                    })
+                }
+                ("Box" | "Arc" | "Rc" | "Cow", [.., inner]) => {
+                    converter(inner, direction, converted)
+                }
+                (ident, []) if converted.contains(ident) => {
+                    Some(format!("{}{ident}", direction.prefix()))
+                }
+                (ident, args) if converted.contains(ident) => {
+                    let args: Vec<String> = args
+                        .iter()
+                        .map(|arg| {
+                            converter(arg, direction, converted)
+                                .unwrap_or_else(|| String::from("(y: any) => y"))
+                        })
+                        .collect();
+                    Some(format!(
+                        "(x: any) => {}{ident}(x, {})",
+                        direction.prefix(),
+                        args.join(", ")
+                    ))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the converted models plus the type parameters of a model, which are converted by the
+/// functions passed to its converter, i.e. `decodeT`.
+fn model_scope(model: &RustModel, converted: &BTreeSet<String>) -> BTreeSet<String> {
+    let mut scope = converted.clone();
+    scope.extend(model.generics.iter().cloned());
+    scope
+}
+
+/// Returns true if any field is renamed or holds a value needing conversion.
+fn fields_need_conversion(
+    fields: &[ModelField],
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+) -> bool {
+    fields.iter().any(|field| {
+        field_name(field, options) != field.wire_name
+            || converter(&field.ty, Direction::Decode, converted).is_some()
+    })
+}
+
+/// Returns the names of the models whose TypeScript representation differs from their JSON,
+/// because a naming convention renamed some of their fields.
+pub fn converted_models(models: &[&RustModel], options: &GeneratorOptions) -> BTreeSet<String> {
+    let mut converted = BTreeSet::new();
+    if options.naming.is_none() || !options.models {
+        return converted;
+    }
+
+    // Models referencing a converted model need conversion too, so repeat until stable.
+    loop {
+        let before = converted.len();
+        for model in models {
+            if converted.contains(&model.name) {
+                continue;
+            }
+            let scope = model_scope(model, &converted);
+
+            let needs_conversion = match &model.kind {
+                ModelKind::Struct(fields) => fields_need_conversion(fields, options, &scope),
+                ModelKind::Newtype(inner) => converter(inner, Direction::Decode, &scope).is_some(),
+                ModelKind::NumericEnum(_) => false,
+                ModelKind::Enum(variants, _) => {
+                    variants.iter().any(|variant| match &variant.fields {
+                        VariantFields::Unit => false,
+                        VariantFields::Tuple(types) => types
+                            .iter()
+                            .any(|ty| converter(ty, Direction::Decode, &scope).is_some()),
+                        VariantFields::Named(fields) => {
+                            fields_need_conversion(fields, options, &scope)
+                        }
+                    })
+                }
+            };
+            if needs_conversion {
+                converted.insert(model.name.clone());
+            }
+        }
+        if converted.len() == before {
+            return converted;
+        }
+    }
+}
+
+/// Renders an object literal converting the fields of `object`.
+fn fields_object(
+    fields: &[ModelField],
+    object: &str,
+    direction: Direction,
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+) -> String {
+    let members: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            let ts_name = field_name(field, options);
+            let (from, to) = match direction {
+                Direction::Decode => (&field.wire_name, &ts_name),
+                Direction::Encode => (&ts_name, &field.wire_name),
+            };
+            let mut f = converter(&field.ty, direction, converted);
+            if field.optional {
+                f = f.map(|f| format!("(x: any) => x == null ? x : {}", call(&f, "x")));
+            }
+            format!("{}: {}", property_key(to), apply(&f, &access(object, from)))
+        })
+        .collect();
+
+    format!("{{ {} }}", members.join(", "))
+}
+
+/// Renders the data of a variant converted, None if it needs no conversion.
+///
+/// # Arguments
+///
+/// * `variant` - The enum variant.
+/// * `value` - The expression holding the data, i.e. `v.Closed`.
+/// * `direction` - Which way the value crosses the wire.
+/// * `options` - The generator options.
+/// * `converted` - The models needing conversion, see `converted_models`.
+fn variant_data(
+    variant: &ModelVariant,
+    value: &str,
+    direction: Direction,
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+) -> Option<String> {
+    match &variant.fields {
+        VariantFields::Unit => None,
+        VariantFields::Tuple(types) if types.len() == 1 => {
+            converter(&types[0], direction, converted).map(|f| call(&f, value))
+        }
+        VariantFields::Tuple(types) => {
+            let elems: Vec<Option<String>> = types
+                .iter()
+                .map(|ty| converter(ty, direction, converted))
+                .collect();
+            if elems.iter().all(Option::is_none) {
+                return None;
+            }
+            let elems: Vec<String> = elems
+                .iter()
+                .enumerate()
+                .map(|(i, f)| apply(f, &format!("{value}[{i}]")))
+                .collect();
+            Some(format!("[{}]", elems.join(", ")))
+        }
+        VariantFields::Named(fields) => fields_need_conversion(fields, options, converted)
+            .then(|| fields_object(fields, value, direction, options, converted)),
+    }
+}
+
+/// Renders the statement converting an enum value of one variant, in the representation of its
+/// enum. Empty if the variant needs no conversion.
+///
+/// i.e. `if ("Closed" in v) return { Closed: decodeReason(v.Closed) };` externally tagged, or
+/// `if (v.type === "Closed") return { ...decodeReason(v), type: v.type };` with `tag = "type"`
+///
+/// Untagged variants are told apart like serde does, by trying them in order, but only struct
+/// variants are recognized, by the presence of their required fields.
+fn variant_conversion(
+    variant: &ModelVariant,
+    tagging: &EnumTagging,
+    direction: Direction,
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+) -> String {
+    let wire_name = &variant.wire_name;
+    match tagging {
+        EnumTagging::External => {
+            let value = access("v", wire_name);
+            match variant_data(variant, &value, direction, options, converted) {
+                Some(data) => format!(
+                    "\tif ({wire_name:?} in v) return {{ {}: {data} }};\n",
+                    property_key(wire_name)
+                ),
+                None => String::new(),
+            }
+        }
+        EnumTagging::Internal { tag } => {
+            let tag_member = format!("{}: {}", property_key(tag), access("v", tag));
+            let object = match &variant.fields {
+                // The fields sit next to the tag.
+                VariantFields::Named(_) => {
+                    variant_data(variant, "v", direction, options, converted).map(|data| {
+                        let members = data.trim_start_matches("{ ").trim_end_matches(" }");
+                        format!("{{ {tag_member}, {members} }}")
+                    })
+                }
+                // serde can't serialize tuple variants with an internal tag.
+                VariantFields::Tuple(types) if types.len() > 1 => None,
+                _ => variant_data(variant, "v", direction, options, converted)
+                    .map(|data| format!("{{ ...{data}, {tag_member} }}")),
+            };
+            match object {
+                Some(object) => format!(
+                    "\tif ({} === {wire_name:?}) return {object};\n",
+                    access("v", tag)
+                ),
+                None => String::new(),
+            }
+        }
+        EnumTagging::Adjacent { tag, content } => {
+            let value = access("v", content);
+            match variant_data(variant, &value, direction, options, converted) {
+                Some(data) => format!(
+                    "\tif ({} === {wire_name:?}) return {{ {}: {}, {}: {data} }};\n",
+                    access("v", tag),
+                    property_key(tag),
+                    access("v", tag),
+                    property_key(content)
+                ),
+                None => String::new(),
+            }
+        }
+        EnumTagging::Untagged => {
+            let VariantFields::Named(fields) = &variant.fields else {
+                return String::new();
+            };
+            let mut present: Vec<String> = fields
+                .iter()
+                .filter(|field| !field.optional)
+                .map(|field| {
+                    let name = match direction {
+                        Direction::Decode => field.wire_name.clone(),
+                        Direction::Encode => field_name(field, options),
+                    };
+                    format!("{name:?} in v")
+                })
+                .collect();
+            if present.is_empty() {
+                present.push(String::from("true"));
+            }
+            // Variants needing no conversion still shadow the ones after them.
+            let data = variant_data(variant, "v", direction, options, converted)
+                .unwrap_or_else(|| String::from("v"));
+            format!("\tif ({}) return {data};\n", present.join(" && "))
+        }
+    }
+}
+
+/// Generates the `decode<Model>` and `encode<Model>` functions of the generated client.
+///
+/// Functions of generic models take an extra converter per type parameter, i.e.
+/// `decodePaginated<T>(v: any, decodeT: (v: any) => T): Paginated<T>`.
+///
+/// # Arguments
+///
+/// * `models` - All models emitted with the interface.
+/// * `options` - The generator options.
+/// * `converted` - The models needing conversion, see `converted_models`.
+pub fn wire_converters(
+    models: &[&RustModel],
+    options: &GeneratorOptions,
+    converted: &BTreeSet<String>,
+) -> String {
+    let mut ts = String::new();
+
+    for model in models.iter().filter(|m| converted.contains(&m.name)) {
+        let scope = model_scope(model, converted);
+        let converted = &scope;
+        let model_type = model.generic_name();
+
+        for direction in [Direction::Decode, Direction::Encode] {
+            let (from, to) = match direction {
+                Direction::Decode => ("any", model_type.as_str()),
+                Direction::Encode => (model_type.as_str(), "any"),
+            };
+            let mut params = vec![format!("v: {from}")];
+            for param in &model.generics {
+                params.push(match direction {
+                    Direction::Decode => format!("decode{param}: (v: any) => {param}"),
+                    Direction::Encode => format!("encode{param}: (v: {param}) => any"),
+                });
+            }
+            let type_params = match model.generics.is_empty() {
+                true => String::new(),
+                false => format!("<{}>", model.generics.join(", ")),
+            };
+            ts.push_str(&format!(
+                "\nfunction {}{}{type_params}({}): {to} {{\n",
+                direction.prefix(),
+                model.name,
+                params.join(", ")
+            ));
+
+            match &model.kind {
+                ModelKind::Struct(fields) => {
+                    ts.push_str(&format!(
+                        "\treturn {};\n",
+                        fields_object(fields, "v", direction, options, converted)
+                    ));
+                }
+                ModelKind::Newtype(inner) => {
+                    let f = converter(inner, direction, converted);
+                    ts.push_str(&format!("\treturn {};\n", apply(&f, "v")));
+                }
+                ModelKind::NumericEnum(_) => ts.push_str("\treturn v;\n"),
+                ModelKind::Enum(variants, tagging) => {
+                    ts.push_str("\tif (typeof v !== \"object\" || v === null) return v;\n");
+                    for variant in variants {
+                        ts.push_str(&variant_conversion(
+                            variant, tagging, direction, options, converted,
+                        ));
+                    }
+                    ts.push_str("\treturn v;\n");
+                }
+            }
+
+            ts.push_str("}\n");
+        }
+    }
+
+    ts
+}
@@ -3,34 +3,36 @@
 // Assumptions:
 //  * Handlers return a single TypeShare'd type
 
-use clap::{Parser, Subcommand};
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
 use std::{fs, path::PathBuf};
-use syn::{visit::Visit, ReturnType};
 
-use log::debug;
+use generator::api_diff::{api_diff, diff_report, Severity};
+use generator::api_interface::{api_interface, file_conflicts, module_files, Layout};
+use generator::inspection::{inspection, Inspection};
+use generator::json_schema::json_schema;
+use generator::msw_handlers::msw_handlers;
+use generator::options::{ClientTarget, EnumStyle, GeneratorOptions, HooksTarget, OutputFormat};
+use log::{debug, warn};
+use parser::cache::{content_hash, Extraction, ParseCache};
+use parser::case_conversion::Naming;
+use parser::cfg_predicate::CfgSet;
 use parser::exclusion_parser::parse_exclusion_file;
-use parser::inner_box_type::inner_box_type;
-use parser::inner_type_from_path_segment::inner_type_from_path_segment;
-use parser::params_as_comma_seperated::params_as_comma_separated_str;
-use parser::visitor::Visitor;
+use parser::handler_names::{find_module_collisions, resolve_collisions, CollisionStrategy};
+use parser::source_module::{module_name, module_path, SourceModule};
+use parser::source_tree::SourceTree;
+use parser::symbol_table::SymbolTable;
+use parser::url_types::parse_url_type;
+use parser::visitor::ModuleCfg;
+use parser::workspace::workspace_crates;
+use rayon::prelude::*;
 
+mod generator;
 mod parser;
 
-/// Provide the inner return type of a request handler that TypeScript callers will care about.
-///
-/// i.e. Result<Json<Message>> -> "Message"
-fn inner_return_type(node: &ReturnType) -> String {
-    if let ReturnType::Type(_, type_box) = node {
-        if let Some(inner) = inner_box_type(type_box) {
-            return inner;
-        }
-    }
-
-    String::from("any") // Unit types and other return types that don't match the assumption of a single TypeShare'd type.
-}
-
 #[derive(Debug, Parser)]
 #[command(name = "rts")]
 #[command(bin_name = "rocket-ts")]
@@ -40,18 +42,180 @@ struct Cli {
     command: Commands,
 }
 
+/// The options selecting and interpreting the Rust sources, shared by all subcommands.
+#[derive(Debug, Clone, Args)]
+struct InputArgs {
+    #[clap(
+        help = "Input directory or file to parse for interface generation. May be repeated.",
+        short = 'i',
+        long = "input",
+        value_name = "INPUT"
+    )]
+    inputs: Vec<PathBuf>,
+
+    #[clap(
+        help = "Cargo.toml of a workspace or package whose crates and path dependencies to parse, resolving paths across them.",
+        long = "manifest-path",
+        value_name = "PATH"
+    )]
+    manifest_path: Option<PathBuf>,
+
+    #[clap(
+        required = false,
+        help = "File listing parameters to exclude (e.g., Request Guards).",
+        short = 'e',
+        long = "exclude-type",
+        value_name = "EXCLUDE"
+    )]
+    exclude_file: Option<String>,
+
+    #[clap(
+        help = "TypeScript type of a custom path or query value type, e.g. `Kid=number`. Overrides the `string` assumed for types implementing or deriving FromParam, FromSegments or FromFormField.",
+        long = "url-type",
+        value_name = "TYPE=TS_TYPE",
+        value_parser = parse_url_type
+    )]
+    url_type_overrides: Vec<(String, String)>,
+
+    #[clap(
+        help = "Cargo features to treat as enabled when evaluating #[cfg(...)] on handlers and modules.",
+        short = 'F',
+        long = "features",
+        value_name = "FEATURES",
+        value_delimiter = ','
+    )]
+    features: Vec<String>,
+
+    #[clap(
+        help = "Configuration option to treat as set, e.g. `debug_assertions` or `target_os=\"linux\"`.",
+        long = "cfg",
+        value_name = "CFG"
+    )]
+    cfgs: Vec<String>,
+
+    #[clap(
+        help = "Directory caching the extraction of unchanged files between runs.",
+        long = "cache-dir",
+        value_name = "DIR",
+        default_value = "target/rocket-ts"
+    )]
+    cache_dir: PathBuf,
+
+    #[clap(
+        help = "Parse every file, neither reading nor writing the cache.",
+        long = "no-cache"
+    )]
+    no_cache: bool,
+
+    #[clap(
+        help = "Enable detailed debug output for troubleshooting",
+        short = 'v',
+        long = "verbose"
+    )]
+    verbose: bool,
+}
+
 #[derive(Debug, Subcommand)]
 enum Commands {
+    /// Generate TypeScript declarations, or a JSON Schema bundle, from the handlers in the input.
     #[command()]
     Generate {
+        #[command(flatten)]
+        input: InputArgs,
+
+        #[clap(
+            help = "Optional output file, or output directory with `--layout files`. STDOUT if not provided.",
+            short = 'o',
+            long = "output",
+            value_name = "OUTPUT"
+        )]
+        output_file: Option<String>,
+
+        #[clap(
+            help = "Also emit declarations for the structs and enums deriving serde traits in the input.",
+            short = 'm',
+            long = "models"
+        )]
+        models: bool,
+
+        #[clap(
+            help = "What to emit: TypeScript, a JSON Schema bundle of the models reachable from the handlers, or MSW request handlers mocking the routes.",
+            short = 'f',
+            long = "format",
+            value_enum,
+            default_value_t = OutputFormat::TypeScript
+        )]
+        format: OutputFormat,
+
         #[clap(
-            required = true,
-            help = "Input directory or file to parse for interface generation.",
-            short = 'i',
-            long = "input",
-            value_name = "INPUT"
+            help = "How unit-only enums are declared with --models.",
+            long = "enum-style",
+            value_enum,
+            default_value_t = EnumStyle::Union
         )]
-        input_dir_or_file: PathBuf,
+        enum_style: EnumStyle,
+
+        #[clap(
+            help = "Name of the exported root interface.",
+            short = 'n',
+            long = "interface-name",
+            value_name = "NAME",
+            default_value = "k7"
+        )]
+        interface_name: String,
+
+        #[clap(
+            help = "How handlers are grouped by source module.",
+            short = 'l',
+            long = "layout",
+            value_enum,
+            default_value_t = Layout::Flat
+        )]
+        layout: Layout,
+
+        #[clap(
+            help = "How handlers sharing a name within the same interface are renamed.",
+            long = "on-collision",
+            value_enum,
+            default_value_t = CollisionStrategy::Error
+        )]
+        on_collision: CollisionStrategy,
+
+        #[clap(
+            help = "Naming convention of emitted handler, parameter and field names. Verbatim if not provided.",
+            long = "naming",
+            value_enum
+        )]
+        naming: Option<Naming>,
+
+        #[clap(
+            help = "Also generate a client calling the handlers.",
+            short = 'c',
+            long = "client",
+            value_enum
+        )]
+        client: Option<ClientTarget>,
+
+        #[clap(
+            help = "Also generate data-fetching hooks calling the client.",
+            long = "hooks",
+            value_enum,
+            requires = "client"
+        )]
+        hooks: Option<HooksTarget>,
+
+        #[clap(
+            help = "Emit handlers compiled out by #[cfg(...)] into a separate interface with this name.",
+            long = "gated-interface",
+            value_name = "NAME"
+        )]
+        gated_interface: Option<String>,
+    },
+    /// Report the handlers and models found in the input, after resolving their types.
+    #[command()]
+    Inspect {
+        #[command(flatten)]
+        input: InputArgs,
 
         #[clap(
             help = "Optional output file. STDOUT if not provided.",
@@ -62,124 +226,501 @@ enum Commands {
         output_file: Option<String>,
 
         #[clap(
-            required = false,
-            help = "File listing parameters to exclude (e.g., Request Guards).",
-            short = 'e',
-            long = "exclude-type",
-            value_name = "EXCLUDE"
+            help = "Emit the report as JSON for other tools, rather than a summary for reading.",
+            long = "json"
+        )]
+        json: bool,
+    },
+    /// Compare two API snapshots and report breaking, non-breaking and additive changes.
+    ///
+    /// Exits with status 1 if any change breaks existing callers.
+    #[command()]
+    Diff {
+        #[clap(
+            required_unless_present = "base",
+            help = "Snapshot written by `inspect --json` that callers were built against.",
+            value_name = "OLD"
+        )]
+        old: Option<PathBuf>,
+
+        #[clap(
+            requires = "old",
+            help = "Snapshot to check. The input is parsed if not provided.",
+            value_name = "NEW"
         )]
-        exclude_file: Option<String>,
+        new: Option<PathBuf>,
 
         #[clap(
-            help = "Enable detailed debug output for troubleshooting",
-            short = 'v',
-            long = "verbose"
+            conflicts_with = "old",
+            help = "Git revision whose input callers were built against, read from the local object store.",
+            long = "base",
+            value_name = "REV"
         )]
-        verbose: bool,
+        base: Option<String>,
+
+        #[command(flatten)]
+        input: InputArgs,
     },
 }
 
+/// Reads a snapshot written by `inspect --json`.
+fn read_snapshot(path: &PathBuf) -> std::io::Result<Inspection> {
+    Inspection::from_json(&fs::read_to_string(path)?)
+        .map_err(|err| std::io::Error::new(err.kind(), format!("{}: {err}", path.display())))
+}
+
+/// The handlers and models of the input, with their types resolved.
+struct Project {
+    /// The handlers and models extracted from each source file, in path order.
+    modules: Vec<SourceModule>,
+    /// The parameter types to leave out of signatures, listed or detected request guards.
+    exclusion_list: Vec<String>,
+    /// The TypeScript types of custom URL value types, see `GeneratorOptions::url_types`.
+    url_types: BTreeMap<String, String>,
+}
+
+/// Writes a generated document to the output file, or STDOUT if there is none.
+fn write_output(output_file: Option<String>, contents: &str) {
+    match output_file {
+        Some(output_file) => {
+            let mut out = File::create(&output_file).expect("Could not create file");
+            out.write_all(contents.as_bytes())
+                .expect("Unable to write data");
+
+            debug!("Exported 🚀 handlers to {}", &output_file);
+        }
+        None => {
+            println!("{}", contents);
+        }
+    }
+}
+
+/// Parses the input files and resolves the types of their handlers and models.
+///
+/// # Arguments
+///
+/// * `input` - The options selecting and interpreting the input files.
+/// * `tree` - Where the input files are read from, i.e. a git revision for `diff --base`.
+fn load_project(input: InputArgs, tree: &SourceTree) -> std::io::Result<Project> {
+    let InputArgs {
+        inputs,
+        manifest_path,
+        exclude_file,
+        url_type_overrides,
+        features,
+        cfgs,
+        cache_dir,
+        no_cache,
+        verbose,
+    } = input;
+    let mut files: Vec<(PathBuf, String)> = vec![];
+
+    if inputs.is_empty() && manifest_path.is_none() {
+        Cli::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "either --input or --manifest-path is required",
+            )
+            .exit();
+    }
+
+    if verbose {
+        std::env::set_var("RUST_LOG", "debug");
+        // `diff --base` loads the input twice, the logger is set up by the first.
+        let _ = env_logger::try_init();
+    }
+
+    for input_dir_or_file in inputs {
+        if tree.is_file(&input_dir_or_file) {
+            let file_path = input_dir_or_file;
+            if let Some(file_name) = file_path.file_name() {
+                if let Some(file_name_str) = file_name.to_str() {
+                    if file_name_str.ends_with(".rs") {
+                        debug!("Loading input file: {}", file_name_str);
+                        files.push((file_path, String::from("crate")));
+                    }
+                }
+            }
+        } else {
+            let folder_path = input_dir_or_file;
+            for file_path in tree.entries(&folder_path)? {
+                if let Some(file_name) = file_path.file_name() {
+                    if let Some(file_name_str) = file_name.to_str() {
+                        if file_name_str.ends_with(".rs") {
+                            debug!("Loading input file: {}", file_name_str);
+                            files.push((file_path, String::from("crate")));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut crate_names = vec![];
+    if let Some(manifest_path) = manifest_path {
+        for krate in workspace_crates(tree, &manifest_path)? {
+            let mut crate_files = vec![];
+            let src = krate.dir.join("src");
+            if tree.is_dir(&src) {
+                tree.source_files(&src, &mut crate_files)?;
+            }
+            debug!(
+                "Loading {} files of crate {}",
+                crate_files.len(),
+                krate.name
+            );
+
+            files.extend(
+                crate_files
+                    .into_iter()
+                    .map(|path| (path, krate.name.clone())),
+            );
+            crate_names.push(krate.name);
+        }
+    }
+
+    let mut exclusion_list = Vec::new();
+
+    if let Some(exclude_file) = exclude_file {
+        debug!("Loading exclusion types from {}", &exclude_file);
+        match parse_exclusion_file(&exclude_file) {
+            Ok(parsed_list) => {
+                debug!("Excluding types {:?}", parsed_list);
+
+                exclusion_list = parsed_list;
+            }
+            Err(err) => {
+                eprintln!("Error reading exclusion file: {}  [{}]", &exclude_file, err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let cfg_set = CfgSet::new(features, cfgs);
+    let mut modules = vec![];
+    let mut symbols = SymbolTable::new(crate_names);
+    let mut url_types = BTreeMap::new();
+    // `diff --base` reads a revision next to the working tree. Each keeps its own cache, so
+    // neither run drops the entries of the other when saving.
+    let cache_dir = match tree {
+        SourceTree::WorkingTree => cache_dir,
+        SourceTree::Revision(_) => cache_dir.join("revision"),
+    };
+    let mut cache = (!no_cache).then(|| ParseCache::load(&cache_dir));
+
+    // Sorted so the output doesn't depend on the order `read_dir` yields files in.
+    files.sort();
+    files.dedup_by(|a, b| a.0 == b.0);
+
+    // Files are parsed in parallel, the results are collected in the sorted order.
+    let extractions: Vec<std::io::Result<(String, serde_json::Value, bool)>> = files
+        .par_iter()
+        .map(|(file_path, crate_name)| {
+            let contents = tree.read_to_string(file_path)?;
+            let hash = content_hash(crate_name, &contents);
+
+            if let Some(json) = cache.as_ref().and_then(|c| c.get(file_path, &hash)) {
+                debug!("Using cached extraction of {}", file_path.display());
+                return Ok((hash, json.clone(), true));
+            }
+
+            let json = Extraction::parse(file_path, crate_name, &contents).to_json()?;
+            Ok((hash, json, false))
+        })
+        .collect();
+
+    let mut loaded = vec![];
+    for ((file_path, crate_name), extraction) in files.into_iter().zip(extractions) {
+        let (hash, mut json, cached) = extraction?;
+        let extraction = match Extraction::from_json(json.clone()) {
+            Ok(extraction) => extraction,
+            Err(err) if cached => {
+                debug!(
+                    "Reparsing {}, cache entry unreadable: {err}",
+                    file_path.display()
+                );
+                let contents = tree.read_to_string(&file_path)?;
+                let extraction = Extraction::parse(&file_path, &crate_name, &contents);
+                json = extraction.to_json()?;
+                extraction
+            }
+            Err(err) => return Err(err),
+        };
+        if let Some(cache) = cache.as_mut() {
+            cache.insert(&file_path, hash, json);
+        }
+        loaded.push((file_path, crate_name, extraction));
+    }
+
+    // `#[cfg(...)] mod admin;` gates every file below `admin`, which only the declaring file knows.
+    let module_cfgs: Vec<ModuleCfg> = loaded
+        .iter_mut()
+        .flat_map(|(_, _, extraction)| std::mem::take(&mut extraction.module_cfgs))
+        .collect();
+
+    for (file_path, crate_name, mut extraction) in loaded {
+        let file_module_path = module_path(&file_path, &crate_name);
+        let file_cfg: Vec<String> = module_cfgs
+            .iter()
+            .filter(|module_cfg| file_module_path.starts_with(&module_cfg.module_path))
+            .flat_map(|module_cfg| module_cfg.cfg.iter().cloned())
+            .collect();
+        if !file_cfg.is_empty() {
+            debug!("File {} is gated by {:?}", file_path.display(), file_cfg);
+            let gate = |cfg: &mut Vec<String>| {
+                cfg.splice(0..0, file_cfg.iter().cloned());
+            };
+            extraction
+                .functions
+                .iter_mut()
+                .for_each(|handler| gate(&mut handler.cfg));
+            extraction
+                .models
+                .iter_mut()
+                .for_each(|model| gate(&mut model.cfg));
+            extraction
+                .aliases
+                .iter_mut()
+                .for_each(|alias| gate(&mut alias.cfg));
+            extraction
+                .imports
+                .iter_mut()
+                .for_each(|import| gate(&mut import.cfg));
+        }
+
+        let file_name_os_str = file_path.file_name().expect("Failed to get file name");
+        let file_name_str = file_name_os_str.to_str().expect("Failed to convert to str");
+
+        let (handlers, gated_handlers): (Vec<_>, Vec<_>) = extraction
+            .functions
+            .into_iter()
+            .partition(|handler| cfg_set.is_active(&handler.cfg));
+        for handler in &gated_handlers {
+            debug!(
+                "Handler {} is compiled out by {:?}",
+                handler.name, handler.cfg
+            );
+        }
+
+        for url_type in extraction.url_types {
+            url_types.insert(url_type, String::from("string"));
+        }
+
+        for guard in extraction.guards {
+            if !exclusion_list.contains(&guard) {
+                debug!("Excluding request guard {guard}");
+                exclusion_list.push(guard);
+            }
+        }
+
+        symbols.extend(
+            extraction
+                .aliases
+                .into_iter()
+                .filter(|alias| cfg_set.is_active(&alias.cfg))
+                .collect(),
+            extraction
+                .imports
+                .into_iter()
+                .filter(|import| cfg_set.is_active(&import.cfg))
+                .collect(),
+        );
+
+        modules.push(SourceModule {
+            name: module_name(&file_path, &crate_name),
+            file_name: file_name_str.to_string(),
+            path: file_path.clone(),
+            handlers,
+            gated_handlers,
+            models: extraction
+                .models
+                .into_iter()
+                .filter(|model| cfg_set.is_active(&model.cfg))
+                .collect(),
+        });
+    }
+
+    url_types.extend(url_type_overrides);
+
+    if let Some(cache) = cache {
+        if let Err(err) = cache.save(&cache_dir) {
+            warn!("Could not write cache to {}: {err}", cache_dir.display());
+        }
+    }
+
+    for module in modules.iter_mut() {
+        symbols.resolve_routes(module);
+        symbols.canonicalize_module(module);
+    }
+
+    Ok(Project {
+        modules,
+        exclusion_list,
+        url_types,
+    })
+}
+
 /// Main function to parse command-line arguments and generate TypeScript interfaces.
 fn main() -> std::io::Result<()> {
     let args = Cli::parse();
-    let mut files: Vec<PathBuf> = vec![];
 
     match args.command {
         Commands::Generate {
-            input_dir_or_file,
+            input,
             output_file,
-            exclude_file,
-            verbose,
+            models,
+            format,
+            enum_style,
+            interface_name,
+            layout,
+            on_collision,
+            naming,
+            client,
+            hooks,
+            gated_interface,
         } => {
-            if verbose {
-                std::env::set_var("RUST_LOG", "debug");
-                env_logger::init();
-            }
+            let Project {
+                mut modules,
+                exclusion_list,
+                url_types,
+            } = load_project(input, &SourceTree::WorkingTree)?;
 
-            if input_dir_or_file.is_file() {
-                let file_path = input_dir_or_file;
-                if let Some(file_name) = file_path.file_name() {
-                    if let Some(file_name_str) = file_name.to_str() {
-                        if file_name_str.ends_with(".rs") {
-                            debug!("Loading input file: {}", file_name_str);
-                            files.push(file_path);
-                        }
+            let collisions = resolve_collisions(&mut modules, layout != Layout::Flat, on_collision);
+            if !collisions.is_empty() {
+                for collision in collisions {
+                    eprintln!(
+                        "error: handler name `{}` is declared more than once",
+                        collision.name
+                    );
+                    for location in collision.locations {
+                        eprintln!("  --> {location}");
                     }
                 }
-            } else {
-                let folder_path = input_dir_or_file;
-                let entries = fs::read_dir(folder_path)?;
-                for entry in entries {
-                    let entry = entry?;
-                    let file_path = entry.path();
-
-                    if let Some(file_name) = file_path.file_name() {
-                        if let Some(file_name_str) = file_name.to_str() {
-                            if file_name_str.ends_with(".rs") {
-                                debug!("Loading input file: {}", file_name_str);
-                                files.push(file_path);
-                            }
+                eprintln!(
+                    "Rename a handler with #[rocket_ts(rename = \"...\")] or pass --on-collision"
+                );
+                std::process::exit(1);
+            }
+
+            if layout != Layout::Flat {
+                let collisions = find_module_collisions(&modules);
+                if !collisions.is_empty() {
+                    for collision in collisions {
+                        eprintln!(
+                            "error: module name `{}` is declared by more than one file",
+                            collision.name
+                        );
+                        for location in collision.locations {
+                            eprintln!("  --> {location}");
                         }
                     }
+                    eprintln!("Rename the files or use --layout flat");
+                    std::process::exit(1);
                 }
             }
 
-            let mut exclusion_list = Vec::new();
+            let options = GeneratorOptions {
+                interface_name,
+                layout,
+                exclusion_list,
+                url_types,
+                models,
+                enum_style,
+                naming,
+                client,
+                hooks,
+                gated_interface,
+            };
 
-            if let Some(exclude_file) = exclude_file {
-                debug!("Loading exclusion types from {}", &exclude_file);
-                match parse_exclusion_file(&exclude_file) {
-                    Ok(parsed_list) => {
-                        debug!("Excluding types {:?}", parsed_list);
+            if layout == Layout::Files && format == OutputFormat::TypeScript {
+                let Some(output_dir) = output_file else {
+                    eprintln!("An output directory (-o) is required with `--layout files`");
+                    std::process::exit(1);
+                };
 
-                        exclusion_list = parsed_list;
-                    }
-                    Err(err) => {
-                        eprintln!("Error reading exclusion file: {}  [{}]", &exclude_file, err);
-                        std::process::exit(1);
+                let conflicts = file_conflicts(&modules);
+                if !conflicts.is_empty() {
+                    for conflict in conflicts {
+                        eprintln!(
+                            "error: module `{0}` would overwrite the generated `{0}.ts`",
+                            conflict.name
+                        );
+                        for location in conflict.locations {
+                            eprintln!("  --> {location}");
+                        }
                     }
+                    eprintln!("Rename the files or choose another --layout");
+                    std::process::exit(1);
                 }
-            }
 
-            let mut ts = r"/*
- * Generated by rocket-ts 0.1.0 🚀 🌎
- */
-export interface k7 {"
-                .to_string();
-
-            for file_path in files {
-                let mut visitor = Visitor { functions: vec![] };
-                let contents = fs::read_to_string(&file_path)?;
-                let syntax = syn::parse_file(&contents).expect("Unable to parse file");
-                visitor.visit_file(&syntax);
-
-                let file_name_os_str = file_path.file_name().expect("Failed to get file name");
-                let file_name_str = file_name_os_str.to_str().expect("Failed to convert to str");
-
-                ts.push_str(&format!("\n\t// {file_name_str}\n"));
-                for handler in visitor.functions {
-                    let params = params_as_comma_separated_str(handler.params, &exclusion_list);
-                    let return_type = inner_return_type(&handler.return_type);
-                    ts.push_str(&format!("\t// handler \"{}\"\n", handler.path));
-                    ts.push_str(&format!(
-                        "\t{}: ({}) => {};\n",
-                        handler.name, params, return_type
-                    ));
+                fs::create_dir_all(&output_dir)?;
+                for (file_name, ts) in module_files(&modules, &options) {
+                    let path = PathBuf::from(&output_dir).join(file_name);
+                    let mut out = File::create(&path).expect("Could not create file");
+                    out.write_all(ts.as_bytes()).expect("Unable to write data");
                 }
+
+                debug!("Exported 🚀 handlers to {}", &output_dir);
+                return Ok(());
             }
 
-            ts.push_str("}\n");
+            let ts = match format {
+                OutputFormat::TypeScript => api_interface(&modules, &options),
+                OutputFormat::JsonSchema => json_schema(&modules, &options),
+                OutputFormat::Msw => msw_handlers(&modules, &options),
+            };
 
-            match output_file {
-                Some(output_file) => {
-                    let mut out = File::create(&output_file).expect("Could not create file");
-                    out.write_all(ts.as_bytes()).expect("Unable to write data");
+            write_output(output_file, &ts);
+        }
+        Commands::Inspect {
+            input,
+            output_file,
+            json,
+        } => {
+            let project = load_project(input, &SourceTree::WorkingTree)?;
+            let inspection = inspection(
+                &project.modules,
+                &project.exclusion_list,
+                &project.url_types,
+            );
 
-                    debug!("Exported 🚀 handlers to {}", &output_file);
-                }
-                None => {
-                    println!("{}", ts);
+            let report = match json {
+                true => inspection.to_json(),
+                false => inspection.summary(),
+            };
+            write_output(output_file, &report);
+        }
+        Commands::Diff {
+            old,
+            new,
+            base,
+            input,
+        } => {
+            let load = |input: InputArgs, tree: &SourceTree| {
+                let project = load_project(input, tree)?;
+                std::io::Result::Ok(inspection(
+                    &project.modules,
+                    &project.exclusion_list,
+                    &project.url_types,
+                ))
+            };
+
+            let (old, new) = match (old, new, base) {
+                (Some(old), Some(new), _) => (read_snapshot(&old)?, read_snapshot(&new)?),
+                (Some(old), None, _) => {
+                    (read_snapshot(&old)?, load(input, &SourceTree::WorkingTree)?)
                 }
+                (None, _, Some(base)) => (
+                    load(input.clone(), &SourceTree::revision(&base)?)?,
+                    load(input, &SourceTree::WorkingTree)?,
+                ),
+                (None, _, None) => unreachable!("clap requires OLD or --base"),
+            };
+
+            let changes = api_diff(&old, &new);
+            println!("{}", diff_report(&changes));
+            if changes.iter().any(|c| c.severity == Severity::Breaking) {
+                std::process::exit(1);
             }
         }
     }
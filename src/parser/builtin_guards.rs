@@ -0,0 +1,60 @@
+use syn::{GenericArgument, PathArguments, Type};
+
+/// The request guards provided by Rocket and its companion crates, which never come from callers.
+const BUILTIN_GUARDS: [&str; 20] = [
+    // rocket
+    "State",
+    "CookieJar",
+    "Host",
+    "Origin",
+    "IpAddr",
+    "SocketAddr",
+    "Config",
+    "Method",
+    "ContentType",
+    "Accept",
+    "Shutdown",
+    "Limits",
+    "Route",
+    "Rocket",
+    "FlashMessage",
+    "Certificate",
+    // rocket_db_pools and rocket_sync_db_pools
+    "Connection",
+    // rocket_ws
+    "WebSocket",
+    // rocket_dyn_templates
+    "Metadata",
+    // rocket_oauth2
+    "TokenResponse",
+];
+
+/// Returns true if a handler parameter's type is a request guard built into Rocket or one of its
+/// companion crates, looking through references, `Option` and `Result`.
+///
+/// i.e. `&State<Config>`, `&CookieJar<'_>`, `Option<&Host>` or `Connection<Db>`
+pub fn is_builtin_guard(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => is_builtin_guard(&reference.elem),
+        Type::Paren(paren) => is_builtin_guard(&paren.elem),
+        Type::Group(group) => is_builtin_guard(&group.elem),
+        Type::Path(type_path) => {
+            let Some(last) = type_path.path.segments.last() else {
+                return false;
+            };
+            let ident = last.ident.to_string();
+
+            if ident == "Option" || ident == "Result" {
+                return match &last.arguments {
+                    PathArguments::AngleBracketed(args) => match args.args.first() {
+                        Some(GenericArgument::Type(inner)) => is_builtin_guard(inner),
+                        _ => false,
+                    },
+                    _ => false,
+                };
+            }
+            BUILTIN_GUARDS.contains(&ident.as_str())
+        }
+        _ => false,
+    }
+}
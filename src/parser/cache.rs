@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use log::debug;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use syn::visit::Visit;
+
+use crate::parser::model::RustModel;
+use crate::parser::source_module::module_path;
+use crate::parser::symbol_table::{TypeAlias, UseImport};
+use crate::parser::visitor::{ModuleCfg, RocketReqHandler, Visitor};
+
+/// The name of the cache file within the cache directory.
+const CACHE_FILE: &str = "cache.json";
+
+/// The version of the extraction, bumped with every change to what `Extraction::parse` yields
+/// for the same file, i.e. a newly recognized derive, as the crate version may stay the same.
+const EXTRACTION_VERSION: u32 = 4;
+
+/// Everything extracted from a single source file, before `#[cfg(...)]` predicates are evaluated.
+#[derive(Serialize, Deserialize)]
+pub struct Extraction {
+    /// The request handlers declared in the file.
+    pub functions: Vec<RocketReqHandler>,
+    /// The structs and enums deriving serde traits.
+    pub models: Vec<RustModel>,
+    /// The `type` aliases, see `SymbolTable`.
+    pub aliases: Vec<TypeAlias>,
+    /// The names brought into scope with `use`.
+    pub imports: Vec<UseImport>,
+    /// The types implementing `FromRequest`, i.e. request guards.
+    pub guards: Vec<String>,
+    /// The types implementing or deriving `FromParam`, `FromSegments` or `FromFormField`.
+    pub url_types: Vec<String>,
+    /// The gated `mod x;` declarations, see `ModuleCfg`.
+    pub module_cfgs: Vec<ModuleCfg>,
+}
+
+impl Extraction {
+    /// Parses a source file of a crate and extracts its handlers, models, aliases and imports.
+    pub fn parse(file_path: &Path, crate_name: &str, contents: &str) -> Extraction {
+        let mut visitor = Visitor::new(module_path(file_path, crate_name));
+        let syntax = syn::parse_file(contents).expect("Unable to parse file");
+        visitor.visit_file(&syntax);
+        Extraction::from(visitor)
+    }
+
+    /// Serializes the extraction to JSON.
+    ///
+    /// Syntax trees can't be sent between threads, so extractions leave the parsing threads in
+    /// this form, which is also how they are cached.
+    pub fn to_json(&self) -> std::io::Result<serde_json::Value> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    /// Parses an extraction back from JSON, see `to_json`.
+    pub fn from_json(json: serde_json::Value) -> std::io::Result<Extraction> {
+        Ok(serde_json::from_value(json)?)
+    }
+}
+
+impl From<Visitor> for Extraction {
+    fn from(visitor: Visitor) -> Extraction {
+        Extraction {
+            functions: visitor.functions,
+            models: visitor.models,
+            aliases: visitor.aliases,
+            imports: visitor.imports,
+            guards: visitor.guards,
+            url_types: visitor.url_types,
+            module_cfgs: visitor.module_cfgs,
+        }
+    }
+}
+
+/// The cached extraction of a file, valid while its content hash matches.
+#[derive(Serialize, Deserialize)]
+struct CachedFile {
+    hash: String,
+    extraction: serde_json::Value,
+}
+
+/// An on-disk cache of the extractions of source files, keyed by path and content hash.
+///
+/// The cache is discarded as a whole when written by another version of rocket-ts or of the
+/// extraction, see `EXTRACTION_VERSION`, since the extraction may differ between them.
+#[derive(Serialize, Deserialize)]
+pub struct ParseCache {
+    version: String,
+    files: BTreeMap<String, CachedFile>,
+    #[serde(skip)]
+    used: BTreeSet<String>,
+}
+
+/// Returns the SHA-256 hash of a file's contents and the name of its crate, which the module
+/// paths of its extraction start with, as a hex string.
+pub fn content_hash(crate_name: &str, contents: &str) -> String {
+    Sha256::new()
+        .chain_update(crate_name.as_bytes())
+        .chain_update([0])
+        .chain_update(contents.as_bytes())
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+impl ParseCache {
+    /// Loads the cache from a directory, or starts an empty one if there is none or it is
+    /// unreadable or outdated.
+    pub fn load(dir: &Path) -> ParseCache {
+        let empty = ParseCache {
+            version: format!("{}+{EXTRACTION_VERSION}", env!("CARGO_PKG_VERSION")),
+            files: BTreeMap::new(),
+            used: BTreeSet::new(),
+        };
+
+        let Ok(contents) = fs::read_to_string(dir.join(CACHE_FILE)) else {
+            return empty;
+        };
+        match serde_json::from_str::<ParseCache>(&contents) {
+            Ok(cache) if cache.version == empty.version => cache,
+            Ok(_) => {
+                debug!("Discarding cache written by another version of rocket-ts");
+                empty
+            }
+            Err(err) => {
+                debug!("Discarding unreadable cache: {err}");
+                empty
+            }
+        }
+    }
+
+    /// Returns the cached extraction of a file as JSON, if its contents are unchanged.
+    ///
+    /// Takes `&self` so files can be looked up while parsing in parallel. Entries not stored
+    /// again with `insert` are dropped when saving.
+    pub fn get(&self, path: &Path, hash: &str) -> Option<&serde_json::Value> {
+        let cached = self.files.get(&path.display().to_string())?;
+        (cached.hash == hash).then_some(&cached.extraction)
+    }
+
+    /// Stores the extraction of a file as JSON, see `Extraction::to_json`.
+    pub fn insert(&mut self, path: &Path, hash: String, extraction: serde_json::Value) {
+        let key = path.display().to_string();
+        self.files
+            .insert(key.clone(), CachedFile { hash, extraction });
+        self.used.insert(key);
+    }
+
+    /// Writes the cache to a directory, dropping the entries of files not seen since loading it.
+    pub fn save(mut self, dir: &Path) -> std::io::Result<()> {
+        let used = std::mem::take(&mut self.used);
+        self.files.retain(|key, _| used.contains(key));
+
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(CACHE_FILE), serde_json::to_string(&self)?)
+    }
+}
@@ -0,0 +1,87 @@
+use clap::ValueEnum;
+
+/// The naming convention of identifiers emitted to TypeScript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Naming {
+    #[value(name = "camelCase")]
+    Camel,
+    #[value(name = "snake_case")]
+    Snake,
+    #[value(name = "PascalCase")]
+    Pascal,
+}
+
+/// Splits an identifier into lowercase words on `_`, `-` and lower-to-upper case boundaries.
+///
+/// i.e. "kid_or_ticketMask" -> ["kid", "or", "ticket", "mask"]
+fn words(ident: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut prev_lower = false;
+
+    for c in ident.chars() {
+        if c == '_' || c == '-' {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        word.extend(c.to_lowercase());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+impl Naming {
+    /// Converts an identifier to the naming convention.
+    ///
+    /// A leading `_` (e.g. on unused parameters) is dropped.
+    pub fn apply(&self, ident: &str) -> String {
+        let words = words(ident);
+        match self {
+            Naming::Snake => words.join("_"),
+            Naming::Pascal => words.iter().map(|w| capitalize(w)).collect(),
+            Naming::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| if i == 0 { w.clone() } else { capitalize(w) })
+                .collect(),
+        }
+    }
+}
+
+/// Applies a serde `rename_all` rule to a field or variant name.
+///
+/// i.e. ("camelCase", "ticket_mask") -> "ticketMask"
+///
+/// Unknown rules leave the name unchanged.
+pub fn serde_rename_all(rule: &str, ident: &str) -> String {
+    let words = words(ident);
+    match rule {
+        "lowercase" => ident.to_lowercase(),
+        "UPPERCASE" => ident.to_uppercase(),
+        "PascalCase" => Naming::Pascal.apply(ident),
+        "camelCase" => Naming::Camel.apply(ident),
+        "snake_case" => words.join("_"),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        "kebab-case" => words.join("-"),
+        "SCREAMING-KEBAB-CASE" => words.join("-").to_uppercase(),
+        _ => ident.to_string(),
+    }
+}
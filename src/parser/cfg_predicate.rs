@@ -0,0 +1,123 @@
+use quote::ToTokens;
+use syn::{Attribute, Lit, Meta, NestedMeta};
+
+/// The configuration options `#[cfg(...)]` predicates are evaluated against.
+///
+/// Nothing is set by default, so `test`, `debug_assertions` and every feature are off, as in a
+/// release build without features.
+#[derive(Debug, Default)]
+pub struct CfgSet {
+    /// The enabled Cargo features.
+    pub features: Vec<String>,
+    /// Options set with `--cfg name`, e.g. `debug_assertions`.
+    pub flags: Vec<String>,
+    /// Options set with `--cfg name="value"`, e.g. `target_os="linux"`.
+    pub pairs: Vec<(String, String)>,
+}
+
+impl CfgSet {
+    /// Builds the set from the `--features` and `--cfg` arguments.
+    pub fn new(features: Vec<String>, cfgs: Vec<String>) -> CfgSet {
+        let mut set = CfgSet {
+            features,
+            ..CfgSet::default()
+        };
+
+        for cfg in cfgs {
+            match cfg.split_once('=') {
+                Some((name, value)) => set.pairs.push((
+                    name.trim().to_string(),
+                    value.trim().trim_matches('"').to_string(),
+                )),
+                None => set.flags.push(cfg.trim().to_string()),
+            }
+        }
+
+        set
+    }
+
+    /// Evaluates a single predicate, e.g. `all(feature = "admin", not(test))`.
+    ///
+    /// Unparseable predicates evaluate to false.
+    pub fn eval(&self, predicate: &str) -> bool {
+        match syn::parse_str::<NestedMeta>(predicate) {
+            Ok(NestedMeta::Meta(meta)) => self.eval_meta(&meta),
+            _ => false,
+        }
+    }
+
+    /// Returns true if all predicates hold, i.e. the item is compiled in.
+    pub fn is_active(&self, predicates: &[String]) -> bool {
+        predicates.iter().all(|p| self.eval(p))
+    }
+
+    fn eval_meta(&self, meta: &Meta) -> bool {
+        match meta {
+            Meta::Path(path) => path
+                .get_ident()
+                .is_some_and(|ident| self.flags.contains(&ident.to_string())),
+            Meta::NameValue(name_value) => {
+                let Lit::Str(value) = &name_value.lit else {
+                    return false;
+                };
+                let Some(name) = name_value.path.get_ident().map(|i| i.to_string()) else {
+                    return false;
+                };
+
+                if name == "feature" {
+                    self.features.contains(&value.value())
+                } else {
+                    self.pairs.contains(&(name, value.value()))
+                }
+            }
+            Meta::List(list) => {
+                let mut nested = list.nested.iter().map(|n| match n {
+                    NestedMeta::Meta(meta) => self.eval_meta(meta),
+                    NestedMeta::Lit(_) => false,
+                });
+
+                if list.path.is_ident("all") {
+                    nested.all(|b| b)
+                } else if list.path.is_ident("any") {
+                    nested.any(|b| b)
+                } else if list.path.is_ident("not") {
+                    list.nested.len() == 1 && !nested.all(|b| b)
+                } else {
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Collects the predicates of the `#[cfg(...)]` attributes of an item.
+///
+/// # Returns
+///
+/// The predicates as source text, e.g. `feature = "admin"`.
+pub fn cfg_predicates(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("cfg"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) if list.nested.len() == 1 => {
+                let predicate = list.nested[0].to_token_stream().to_string();
+                Some(
+                    predicate
+                        .replace("all (", "all(")
+                        .replace("any (", "any(")
+                        .replace("not (", "not("),
+                )
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Renders predicates as a single `cfg(...)`, e.g. for a comment on the generated member.
+pub fn cfg_display(predicates: &[String]) -> String {
+    match predicates {
+        [predicate] => format!("cfg({predicate})"),
+        _ => format!("cfg(all({}))", predicates.join(", ")),
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use syn::{Attribute, Lit, Meta, NestedMeta};
+
+use crate::parser::rocket_ts_attr::DOC_DIRECTIVE;
+
+/// Documentation carried over from a Rust item.
+///
+/// Holds the `///` (or `#[doc = "..."]`) lines of an item together with the note of a
+/// `#[deprecated]` attribute, if present.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Docs {
+    /// The documentation lines, with the leading space of `///` comments removed.
+    pub lines: Vec<String>,
+    /// `Some` if the item is `#[deprecated]`. Holds the `note`, which may be empty.
+    pub deprecated: Option<String>,
+}
+
+/// Collects the doc comments and deprecation note from a list of attributes.
+///
+/// # Arguments
+///
+/// * `attrs` - The attributes of a function, struct, field or enum variant.
+///
+/// # Returns
+///
+/// The `Docs` for the item. Leading and trailing blank lines are dropped, as are
+/// `@rocket-ts` directives.
+pub fn doc_comments(attrs: &[Attribute]) -> Docs {
+    let mut docs = Docs::default();
+
+    for attr in attrs {
+        if attr.path.is_ident("doc") {
+            if let Ok(Meta::NameValue(name_value)) = attr.parse_meta() {
+                if let Lit::Str(lit) = name_value.lit {
+                    for line in lit.value().split('\n') {
+                        if line.trim().starts_with(DOC_DIRECTIVE) {
+                            continue;
+                        }
+                        let line = line.strip_prefix(' ').unwrap_or(line);
+                        docs.lines.push(line.trim_end().to_string());
+                    }
+                }
+            }
+        } else if attr.path.is_ident("deprecated") {
+            docs.deprecated = Some(deprecation_note(attr));
+        }
+    }
+
+    while docs.lines.first().is_some_and(|l| l.is_empty()) {
+        docs.lines.remove(0);
+    }
+    while docs.lines.last().is_some_and(|l| l.is_empty()) {
+        docs.lines.pop();
+    }
+
+    docs
+}
+
+/// Extracts the note from `#[deprecated = "..."]` or `#[deprecated(note = "...")]`.
+fn deprecation_note(attr: &Attribute) -> String {
+    match attr.parse_meta() {
+        Ok(Meta::NameValue(name_value)) => {
+            if let Lit::Str(lit) = name_value.lit {
+                return lit.value();
+            }
+        }
+        Ok(Meta::List(list)) => {
+            for nested in list.nested {
+                if let NestedMeta::Meta(Meta::NameValue(name_value)) = nested {
+                    if name_value.path.is_ident("note") {
+                        if let Lit::Str(lit) = name_value.lit {
+                            return lit.value();
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    String::new()
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use clap::ValueEnum;
+
+use crate::parser::source_module::SourceModule;
+
+/// How handlers sharing a name within the same interface are renamed.
+///
+/// Names given explicitly with `#[rocket_ts(rename = "...")]` are never changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CollisionStrategy {
+    /// Report the collision and stop.
+    Error,
+    /// Prefix the colliding names with the name of their innermost module, e.g. `admin_get_list`
+    /// for a handler in `mod admin`, or `thread_get_list` for one at the top of `thread.rs`.
+    /// Handlers at the root of a workspace crate are prefixed with the crate name, those at the
+    /// root of an `--input` crate keep their name.
+    ModulePrefix,
+    /// Derive the colliding names from method and route, e.g. `get_thread_by_kid`.
+    Route,
+}
+
+/// A handler or module name declared more than once within the same interface.
+pub struct Collision {
+    /// The colliding name.
+    pub name: String,
+    /// The `file:line` and Rust path of every handler declaring it, or the path of every file
+    /// declaring the module.
+    pub locations: Vec<String>,
+}
+
+/// Derives a handler name from its HTTP method and route.
+///
+/// i.e. get "/thread/<thread_id>/comments?<page>" -> "get_thread_by_thread_id_comments"
+pub fn route_name(method: &str, path: &str) -> String {
+    let path = path.split('?').next().unwrap_or_default();
+    let mut parts = vec![method.to_lowercase()];
+
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        let segment = match segment.strip_prefix('<') {
+            Some(param) => format!("by_{}", param.trim_end_matches(['>', '.'])),
+            None => segment.to_string(),
+        };
+        parts.push(
+            segment
+                .chars()
+                .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                .collect(),
+        );
+    }
+
+    parts.join("_")
+}
+
+/// Finds the handler names declared more than once within the same interface.
+///
+/// # Arguments
+///
+/// * `modules` - The handlers extracted from each source file.
+/// * `per_module` - True if each module gets its own interface (nested and files layouts), false
+///   if all handlers share the root interface.
+pub fn find_collisions(modules: &[SourceModule], per_module: bool) -> Vec<Collision> {
+    let mut scopes: Vec<BTreeMap<&str, Vec<String>>> = vec![];
+
+    for (i, module) in modules.iter().enumerate() {
+        if i == 0 || per_module {
+            scopes.push(BTreeMap::new());
+        }
+        let scope = scopes.last_mut().unwrap();
+        for handler in &module.handlers {
+            scope
+                .entry(handler.name.as_str())
+                .or_default()
+                .push(format!(
+                    "{}:{} ({})",
+                    module.path.display(),
+                    handler.line,
+                    handler.rust_path
+                ));
+        }
+    }
+
+    scopes
+        .into_iter()
+        .flatten()
+        .filter(|(_, locations)| locations.len() > 1)
+        .map(|(name, locations)| Collision {
+            name: name.to_string(),
+            locations,
+        })
+        .collect()
+}
+
+/// Finds the module names declared by more than one file, i.e. `thread.rs` in two crates.
+///
+/// Each module becomes a member of the root interface in the nested and files layouts, so their
+/// names must be unique there.
+pub fn find_module_collisions(modules: &[SourceModule]) -> Vec<Collision> {
+    let mut names: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for module in modules {
+        names
+            .entry(module.name.as_str())
+            .or_default()
+            .push(module.path.display().to_string());
+    }
+
+    names
+        .into_iter()
+        .filter(|(_, locations)| locations.len() > 1)
+        .map(|(name, locations)| Collision {
+            name: name.to_string(),
+            locations,
+        })
+        .collect()
+}
+
+/// Renames colliding handlers according to the strategy.
+///
+/// # Returns
+///
+/// The collisions that remain, e.g. because the strategy is `CollisionStrategy::Error` or two
+/// handlers still derive the same name.
+pub fn resolve_collisions(
+    modules: &mut [SourceModule],
+    per_module: bool,
+    strategy: CollisionStrategy,
+) -> Vec<Collision> {
+    let collisions = find_collisions(modules, per_module);
+    if collisions.is_empty() || strategy == CollisionStrategy::Error {
+        return collisions;
+    }
+
+    let colliding: Vec<String> = collisions.into_iter().map(|c| c.name).collect();
+
+    for module in modules.iter_mut() {
+        for handler in module.handlers.iter_mut() {
+            if handler.explicit_name || !colliding.contains(&handler.name) {
+                continue;
+            }
+
+            handler.name = match strategy {
+                CollisionStrategy::ModulePrefix => match handler.module_path.last() {
+                    // The root of a crate given with `--input` has no name to prefix with, the
+                    // handlers it collides with are renamed instead.
+                    Some(name) if name != "crate" && name != "src" => {
+                        format!("{}_{}", name, handler.name)
+                    }
+                    _ => continue,
+                },
+                CollisionStrategy::Route => route_name(&handler.method, &handler.path),
+                CollisionStrategy::Error => unreachable!(),
+            };
+        }
+    }
+
+    find_collisions(modules, per_module)
+}
@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+
+use log::debug;
+use serde::{Deserialize, Serialize};
+use syn::{FnArg, Type};
+
+use crate::parser::builtin_guards::is_builtin_guard;
+use crate::parser::exclusion_parser::should_exclude_type;
+use crate::parser::ts_type::ts_type;
+use crate::parser::url_types::url_ts_type;
+use crate::parser::visitor::RocketReqHandler;
+
+/// Where the value of a handler parameter comes from in the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamKind {
+    /// A dynamic path segment, i.e. `<id>`.
+    Path,
+    /// Multiple trailing path segments, i.e. `<path..>`.
+    Segments,
+    /// A single query parameter, i.e. `?<page>`.
+    Query,
+    /// A struct whose fields are query parameters, i.e. `?<filters..>`.
+    QueryFields,
+    /// The request body, i.e. `data = "<body>"`.
+    Body,
+    /// Not named in the route attribute, so Rocket treats it as a request guard.
+    Guard,
+}
+
+/// How a request body is encoded, by its data guard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyFormat {
+    /// `Json<T>`, and bodies read by other data guards.
+    Json,
+    /// `Form<T>`, `Strict<T>` or `Lenient<T>`, sent url-encoded, or as multipart if it holds files.
+    Form,
+    /// `MsgPack<T>`, encoded by the caller and sent as a `Blob`, like MessagePack responses.
+    MsgPack,
+}
+
+/// A parameter of a request handler as seen by TypeScript callers.
+#[derive(Clone)]
+pub struct HandlerParam {
+    /// The name of the parameter in Rust, which is also its name in the query string.
+    pub name: String,
+    /// The TypeScript type of the parameter.
+    pub ts_type: String,
+    /// The Rust type of the value, with `Json<T>` and similar wrappers removed.
+    pub ty: Type,
+    /// Where the value comes from in the request.
+    pub kind: ParamKind,
+    /// True for `Option<T>` query parameters, which callers may leave out.
+    pub optional: bool,
+    /// How the value is encoded, for the request body.
+    pub body_format: Option<BodyFormat>,
+}
+
+/// Returns the data guard wrapping a type and the type it wraps, i.e. `Json<T>` -> (`Json`, `T`).
+fn data_guard(ty: &Type) -> Option<(String, &Type)> {
+    if let Type::Path(type_path) = ty {
+        if let Some(last) = type_path.path.segments.last() {
+            let ident = last.ident.to_string();
+            if matches!(
+                ident.as_str(),
+                "Json" | "Form" | "MsgPack" | "Strict" | "Lenient"
+            ) {
+                if let syn::PathArguments::AngleBracketed(args) = &last.arguments {
+                    if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                        return Some((ident, inner));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Removes the data guards wrapping a deserialized value.
+///
+/// i.e. `Json<T>` -> `T`, `Form<Strict<T>>` -> `T`
+fn unwrap_data_guard(ty: &Type) -> &Type {
+    match data_guard(ty) {
+        Some((_, inner)) => unwrap_data_guard(inner),
+        None => ty,
+    }
+}
+
+/// Returns how a request body is encoded by its outermost data guard.
+fn body_format(ty: &Type) -> BodyFormat {
+    match data_guard(ty) {
+        Some((guard, _)) if matches!(guard.as_str(), "Form" | "Strict" | "Lenient") => {
+            BodyFormat::Form
+        }
+        Some((guard, _)) if guard == "MsgPack" => BodyFormat::MsgPack,
+        _ => BodyFormat::Json,
+    }
+}
+
+/// Classifies a parameter by looking up its name in the route and data attribute.
+pub fn param_kind(handler: &RocketReqHandler, name: &str) -> ParamKind {
+    let (path, query) = handler
+        .path
+        .split_once('?')
+        .unwrap_or((handler.path.as_str(), ""));
+
+    if handler.data.as_deref() == Some(name) {
+        ParamKind::Body
+    } else if path.contains(&format!("<{name}>")) {
+        ParamKind::Path
+    } else if path.contains(&format!("<{name}..>")) {
+        ParamKind::Segments
+    } else if query.contains(&format!("<{name}>")) {
+        ParamKind::Query
+    } else if query.contains(&format!("<{name}..>")) {
+        ParamKind::QueryFields
+    } else {
+        ParamKind::Guard
+    }
+}
+
+/// Extracts the parameters of a request handler, excluding specified types.
+///
+/// It handles various types of function arguments including references, path types, and more.
+/// Additionally, it excludes Rocket's own request guards, see `is_builtin_guard`, and types
+/// specified in the provided exclusion list. Path segments and query values are typed as callers
+/// send them, see `url_ts_type`.
+pub fn handler_params(
+    handler: &RocketReqHandler,
+    exclusion_list: &[String],
+    url_types: &BTreeMap<String, String>,
+) -> Vec<HandlerParam> {
+    let mut params = Vec::new();
+
+    for arg in &handler.params {
+        if let FnArg::Typed(syn::PatType { pat, ty, .. }) = arg {
+            let pat_ident = match &**pat {
+                syn::Pat::Ident(pat_ident) => pat_ident,
+                _ => continue,
+            };
+
+            let param_name = pat_ident.ident.to_string();
+            let kind = param_kind(handler, &param_name);
+            let mut optional = false;
+
+            debug!("Examining param <{param_name}>");
+
+            if kind == ParamKind::Guard && is_builtin_guard(ty) {
+                debug!("Excluding built-in request guard <{param_name}>");
+                continue;
+            }
+
+            // Borrowed values, i.e. `&str` or `&Kid`, are typed like owned ones.
+            let owned = match &**ty {
+                Type::Reference(reference) => &*reference.elem,
+                ty => ty,
+            };
+
+            let param_type = match owned {
+                Type::Path(type_path) => {
+                    let path = &type_path.path;
+                    let last = path.segments.iter().last().unwrap();
+
+                    let inner_type = unwrap_data_guard(owned);
+                    if kind == ParamKind::Body && body_format(owned) == BodyFormat::MsgPack {
+                        String::from("Blob")
+                    } else if !std::ptr::eq(inner_type, owned) {
+                        ts_type(inner_type)
+                    } else {
+                        if should_exclude_type(last.ident.to_string(), exclusion_list) {
+                            debug!("Excluding param {param_name}:{}", last.ident);
+                            continue;
+                        }
+
+                        let ts = match kind {
+                            ParamKind::Path | ParamKind::Segments | ParamKind::Query => {
+                                url_ts_type(owned, url_types)
+                            }
+                            _ => ts_type(owned),
+                        };
+                        match (kind, ts.strip_suffix(" | null")) {
+                            (ParamKind::Query, Some(inner)) => {
+                                optional = true;
+                                inner.to_string()
+                            }
+                            _ => ts,
+                        }
+                    }
+                }
+                _ => {
+                    debug!("Skipping param <{param_name}> due to missing match");
+                    continue;
+                }
+            };
+
+            params.push(HandlerParam {
+                name: param_name,
+                ts_type: param_type,
+                ty: unwrap_data_guard(ty).clone(),
+                kind,
+                optional,
+                body_format: (kind == ParamKind::Body).then(|| body_format(ty)),
+            });
+        } else {
+            debug!("Skipping param w/ Receiver type");
+        }
+    }
+
+    params
+}
+
+/// Returns true if the parameter may be left out by callers.
+///
+/// Only trailing optional parameters can be left out in TypeScript; an optional parameter
+/// followed by a required one must be passed explicitly, e.g. as `undefined`.
+pub fn is_omittable(params: &[HandlerParam], index: usize) -> bool {
+    params[index..].iter().all(|p| p.optional)
+}
@@ -1,5 +1,5 @@
-use syn::Type;
 use log::debug;
+use syn::Type;
 
 /// Extracts the inner parameter type from a boxed Type.
 ///
@@ -13,9 +13,8 @@ use log::debug;
 /// # Returns
 ///
 /// A String representing the inner parameter type extracted from the boxed Type.
-
-pub fn _inner_param(type_box: &Box<Type>) -> String {
-    if let Type::Path(type_path) = &**type_box {
+pub fn _inner_param(type_box: &Type) -> String {
+    if let Type::Path(type_path) = type_box {
         if let Some(segment) = type_path.path.segments.first() {
             if segment.ident == "Result" {
                 match &segment.arguments {
@@ -88,4 +87,4 @@ pub fn _inner_param(type_box: &Box<Type>) -> String {
     }
 
     String::from("any") // Unit types and other return types that don't match the assumption of a single TypeShare'd type.
-}
\ No newline at end of file
+}
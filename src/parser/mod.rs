@@ -1,6 +1,24 @@
-pub mod inner_box_type;
-pub mod inner_type_from_path_segment;
+pub mod builtin_guards;
+pub mod cache;
+pub mod case_conversion;
+pub mod cfg_predicate;
+pub mod doc_comments;
+pub mod exclusion_parser;
+pub mod handler_names;
+pub mod handler_params;
 pub mod inner_params;
+pub mod model;
 pub mod params_as_comma_seperated;
-pub mod exclusion_parser;
-pub mod visitor;
\ No newline at end of file
+pub mod response_kind;
+pub mod response_type;
+pub mod rocket_ts_attr;
+pub mod serde_attrs;
+pub mod source_module;
+pub mod source_tree;
+pub mod symbol_table;
+pub mod token_serde;
+pub mod ts_type;
+pub mod type_args;
+pub mod url_types;
+pub mod visitor;
+pub mod workspace;
@@ -0,0 +1,322 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use syn::{
+    Attribute, Expr, ExprLit, ExprUnary, Fields, Generics, ItemEnum, ItemStruct, Lit, Meta,
+    NestedMeta, Type, UnOp,
+};
+
+use crate::parser::case_conversion::serde_rename_all;
+use crate::parser::doc_comments::{doc_comments, Docs};
+use crate::parser::serde_attrs::serde_attrs;
+use crate::parser::token_serde::{boxed_tokens, token_vec, tokens};
+
+/// Represents a serializable model type (struct or enum) found next to the handlers.
+#[derive(Serialize, Deserialize)]
+pub struct RustModel {
+    /// The name of the struct or enum.
+    pub name: String,
+    /// The names of its type parameters, i.e. `["T"]` for `Paginated<T>`.
+    pub generics: Vec<String>,
+    /// Documentation carried over from the Rust item.
+    pub docs: Docs,
+    /// The shape of the model.
+    pub kind: ModelKind,
+    /// The `#[cfg(...)]` predicates of the model and its enclosing modules.
+    pub cfg: Vec<String>,
+    /// The path of the module declaring the model, see `RocketReqHandler::module_path`.
+    pub module_path: Vec<String>,
+}
+
+/// The shape of a model type.
+#[derive(Serialize, Deserialize)]
+pub enum ModelKind {
+    /// A struct with named fields.
+    Struct(Vec<ModelField>),
+    /// A tuple struct with a single field, serialized as its inner value.
+    Newtype(#[serde(with = "boxed_tokens")] Box<Type>),
+    /// An enum, serialized with the representation chosen by serde's `tag`, `content` and
+    /// `untagged`.
+    Enum(Vec<ModelVariant>, EnumTagging),
+    /// A unit-only enum deriving `serde_repr`'s traits, serialized as its discriminant.
+    NumericEnum(Vec<ModelVariant>),
+}
+
+/// How the variant of an enum is told apart on the wire, see serde's enum representations.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum EnumTagging {
+    /// The default, i.e. `{ "Closed": reason }`.
+    External,
+    /// `#[serde(tag = "type")]`, i.e. `{ "type": "Closed", ...fields }`.
+    Internal { tag: String },
+    /// `#[serde(tag = "t", content = "c")]`, i.e. `{ "t": "Closed", "c": reason }`.
+    Adjacent { tag: String, content: String },
+    /// `#[serde(untagged)]`, i.e. `reason`.
+    Untagged,
+}
+
+impl fmt::Display for EnumTagging {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnumTagging::External => write!(f, "externally tagged"),
+            EnumTagging::Internal { tag } => write!(f, "internally tagged by {tag:?}"),
+            EnumTagging::Adjacent { tag, content } => {
+                write!(f, "adjacently tagged by {tag:?} and {content:?}")
+            }
+            EnumTagging::Untagged => write!(f, "untagged"),
+        }
+    }
+}
+
+/// A named field of a struct or struct-like enum variant.
+#[derive(Serialize, Deserialize)]
+pub struct ModelField {
+    /// The name of the field.
+    pub name: String,
+    /// The name of the field in JSON, after applying serde's `rename` and `rename_all`.
+    pub wire_name: String,
+    /// True if the field may be absent from JSON, i.e. `skip_serializing_if`.
+    pub optional: bool,
+    /// Documentation carried over from the field.
+    pub docs: Docs,
+    /// The Rust type of the field.
+    #[serde(with = "tokens")]
+    pub ty: Type,
+}
+
+/// A variant of an enum model.
+#[derive(Serialize, Deserialize)]
+pub struct ModelVariant {
+    /// The name of the variant.
+    pub name: String,
+    /// The name of the variant in JSON, after applying serde's `rename` and `rename_all`.
+    pub wire_name: String,
+    /// The value of a unit variant, explicit or implied by the previous variant, i.e. `2` for
+    /// `High = 2`.
+    pub discriminant: Option<i64>,
+    /// Documentation carried over from the variant.
+    pub docs: Docs,
+    /// The data carried by the variant.
+    pub fields: VariantFields,
+}
+
+/// The data carried by an enum variant.
+#[derive(Serialize, Deserialize)]
+pub enum VariantFields {
+    Unit,
+    Tuple(#[serde(with = "token_vec")] Vec<Type>),
+    Named(Vec<ModelField>),
+}
+
+/// Returns the last segment of each trait the item derives, i.e. `["Debug", "Serialize"]`.
+fn derives(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(meta) => meta.path().segments.last().map(|s| s.ident.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns true if the item derives serde's `Serialize` or `Deserialize`, or their `serde_repr`
+/// counterparts.
+///
+/// Only such items are considered models; services, guards and other structs living next to the
+/// handlers are ignored.
+pub fn derives_serde(attrs: &[Attribute]) -> bool {
+    derives(attrs).iter().any(|d| {
+        matches!(
+            d.as_str(),
+            "Serialize" | "Deserialize" | "Serialize_repr" | "Deserialize_repr"
+        )
+    })
+}
+
+/// Returns true if the item derives Rocket's `FromFormField` or `FromParam`, making it a value
+/// sent as a string in the URL, see `Visitor::url_types`.
+pub fn derives_url_value(attrs: &[Attribute]) -> bool {
+    derives(attrs)
+        .iter()
+        .any(|d| matches!(d.as_str(), "FromFormField" | "FromParam"))
+}
+
+/// Returns true if the item derives `serde_repr`'s `Serialize_repr` or `Deserialize_repr`.
+fn derives_serde_repr(attrs: &[Attribute]) -> bool {
+    derives(attrs)
+        .iter()
+        .any(|d| d == "Serialize_repr" || d == "Deserialize_repr")
+}
+
+/// Returns the integer value of an explicit discriminant, i.e. `2` or `-1`.
+fn discriminant_value(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().ok(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => discriminant_value(expr).map(|value| -value),
+        Expr::Paren(paren) => discriminant_value(&paren.expr),
+        Expr::Group(group) => discriminant_value(&group.expr),
+        _ => None,
+    }
+}
+
+/// Returns the names of the type parameters of an item. Lifetimes and const parameters have no
+/// TypeScript counterpart and are left out.
+fn type_params(generics: &Generics) -> Vec<String> {
+    generics
+        .type_params()
+        .map(|param| param.ident.to_string())
+        .collect()
+}
+
+/// Collects the named fields that serde serializes.
+///
+/// # Arguments
+///
+/// * `fields` - The fields of a struct or enum variant.
+/// * `rename_all` - The `rename_all` rule of the container, if any.
+fn named_fields(fields: &Fields, rename_all: Option<&str>) -> Vec<ModelField> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let ident = field.ident.as_ref()?;
+            let serde = serde_attrs(&field.attrs);
+            if serde.skip {
+                return None;
+            }
+
+            let name = ident.to_string();
+            let name = name.strip_prefix("r#").unwrap_or(&name).to_string();
+            let wire_name = match (serde.rename, rename_all) {
+                (Some(rename), _) => rename,
+                (None, Some(rule)) => serde_rename_all(rule, &name),
+                (None, None) => name.clone(),
+            };
+
+            Some(ModelField {
+                name,
+                wire_name,
+                optional: serde.skip_serializing_if,
+                docs: doc_comments(&field.attrs),
+                ty: field.ty.clone(),
+            })
+        })
+        .collect()
+}
+
+impl RustModel {
+    /// Returns the name of the model with its type parameters, i.e. `Paginated<T>`.
+    pub fn generic_name(&self) -> String {
+        if self.generics.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}<{}>", self.name, self.generics.join(", "))
+        }
+    }
+
+    /// Builds a model from a struct. Unit structs and tuple structs with more than one field are
+    /// not supported and yield `None`.
+    pub fn from_struct(item: &ItemStruct) -> Option<RustModel> {
+        let rename_all = serde_attrs(&item.attrs).rename_all;
+        let kind = match &item.fields {
+            Fields::Named(_) => {
+                ModelKind::Struct(named_fields(&item.fields, rename_all.as_deref()))
+            }
+            Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+                ModelKind::Newtype(Box::new(unnamed.unnamed[0].ty.clone()))
+            }
+            _ => return None,
+        };
+
+        Some(RustModel {
+            name: item.ident.to_string(),
+            generics: type_params(&item.generics),
+            docs: doc_comments(&item.attrs),
+            kind,
+            cfg: vec![],
+            module_path: vec![],
+        })
+    }
+
+    /// Builds a model from an enum.
+    ///
+    /// Variants skipped by serde are left out. Unit-only enums deriving `serde_repr`'s traits
+    /// become a `ModelKind::NumericEnum`.
+    pub fn from_enum(item: &ItemEnum) -> RustModel {
+        let container = serde_attrs(&item.attrs);
+        let rename_all = container.rename_all;
+        let tagging = match (container.untagged, container.tag, container.content) {
+            (true, _, _) => EnumTagging::Untagged,
+            (false, Some(tag), Some(content)) => EnumTagging::Adjacent { tag, content },
+            (false, Some(tag), None) => EnumTagging::Internal { tag },
+            (false, None, _) => EnumTagging::External,
+        };
+        let mut next_discriminant = Some(0);
+
+        let mut variants = vec![];
+        for variant in &item.variants {
+            let discriminant = match &variant.discriminant {
+                Some((_, expr)) => discriminant_value(expr),
+                None => next_discriminant,
+            };
+            next_discriminant = discriminant.and_then(|d| d.checked_add(1));
+
+            let serde = serde_attrs(&variant.attrs);
+            if serde.skip {
+                continue;
+            }
+
+            let name = variant.ident.to_string();
+            let wire_name = match (serde.rename, &rename_all) {
+                (Some(rename), _) => rename,
+                (None, Some(rule)) => serde_rename_all(rule, &name),
+                (None, None) => name.clone(),
+            };
+
+            variants.push(ModelVariant {
+                name,
+                wire_name,
+                discriminant,
+                docs: doc_comments(&variant.attrs),
+                fields: match &variant.fields {
+                    Fields::Unit => VariantFields::Unit,
+                    Fields::Unnamed(unnamed) => {
+                        VariantFields::Tuple(unnamed.unnamed.iter().map(|f| f.ty.clone()).collect())
+                    }
+                    Fields::Named(_) => VariantFields::Named(named_fields(
+                        &variant.fields,
+                        serde.rename_all.as_deref(),
+                    )),
+                },
+            });
+        }
+
+        let numeric = derives_serde_repr(&item.attrs)
+            && variants
+                .iter()
+                .all(|v| matches!(v.fields, VariantFields::Unit) && v.discriminant.is_some());
+
+        RustModel {
+            name: item.ident.to_string(),
+            generics: type_params(&item.generics),
+            docs: doc_comments(&item.attrs),
+            kind: match numeric {
+                true => ModelKind::NumericEnum(variants),
+                false => ModelKind::Enum(variants, tagging),
+            },
+            cfg: vec![],
+            module_path: vec![],
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use syn::{FnArg, ReturnType, Type};
+
+use crate::parser::type_args::first_type_arg;
+use crate::parser::visitor::RocketReqHandler;
+
+/// How a handler's response is consumed by callers.
+pub enum ResponseKind {
+    /// A value serialized as JSON, the default.
+    Json,
+    /// Plain text, i.e. `String`, `&'static str` or `RawText`.
+    Text,
+    /// Bytes or a file, i.e. `Vec<u8>`, `NamedFile` or `MsgPack`.
+    Binary,
+    /// An HTML document, i.e. `RawHtml` or a `Template`.
+    Html,
+    /// A `Redirect`, followed by the client.
+    Redirect,
+    /// No body, i.e. `()`, `Status` or `NoContent`.
+    Empty,
+    /// Server-sent events from `EventStream![]`, carrying the payload type given with
+    /// `#[rocket_ts(event = "...")]`. The raw event data if None.
+    EventStream(Option<Box<Type>>),
+    /// Text chunks from `TextStream![]`.
+    TextStream,
+    /// Byte chunks from `ByteStream![]` or `ReaderStream![]`.
+    ByteStream,
+    /// A websocket from `rocket_ws`, i.e. a handler taking `ws::WebSocket`.
+    WebSocket,
+}
+
+/// Returns true for `u8`, the item type of byte responses.
+fn is_u8(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.is_ident("u8"))
+}
+
+/// Classifies a responder by the content it sends, looking through `Result`, `Option`, status
+/// wrappers like `status::Custom` and `(Status, R)` tuples.
+///
+/// Unknown responders with type arguments are assumed to wrap their first argument, e.g.
+/// `K7Response<T>`; unknown responders without are assumed to send JSON.
+fn content_kind(handler: &RocketReqHandler, ty: &Type) -> ResponseKind {
+    match ty {
+        Type::Reference(reference) => content_kind(handler, &reference.elem),
+        Type::Paren(paren) => content_kind(handler, &paren.elem),
+        Type::Group(group) => content_kind(handler, &group.elem),
+        Type::Slice(slice) if is_u8(&slice.elem) => ResponseKind::Binary,
+        Type::Tuple(tuple) => match tuple.elems.last() {
+            Some(last) => content_kind(handler, last),
+            None => ResponseKind::Empty,
+        },
+        Type::Macro(type_macro) => match type_macro.mac.path.segments.last() {
+            Some(segment) => stream_kind(handler, &segment.ident.to_string()),
+            None => ResponseKind::Json,
+        },
+        Type::Path(type_path) => {
+            let Some(last) = type_path.path.segments.last() else {
+                return ResponseKind::Json;
+            };
+            let arg = first_type_arg(ty);
+
+            match (last.ident.to_string().as_str(), arg) {
+                ("Json" | "RawJson", _) => ResponseKind::Json,
+                ("String" | "str" | "RawText", _) => ResponseKind::Text,
+                ("Vec", Some(item)) if is_u8(item) => ResponseKind::Binary,
+                ("NamedFile" | "File" | "MsgPack" | "RawMsgPack", _) => ResponseKind::Binary,
+                ("RawHtml" | "Template", _) => ResponseKind::Html,
+                ("Redirect", _) => ResponseKind::Redirect,
+                ("Status" | "NoContent", _) => ResponseKind::Empty,
+                (ident @ ("EventStream" | "TextStream" | "ByteStream" | "ReaderStream"), _) => {
+                    stream_kind(handler, ident)
+                }
+                ("Vec" | "VecDeque" | "HashMap" | "BTreeMap" | "HashSet" | "BTreeSet", _) => {
+                    ResponseKind::Json
+                }
+                (_, Some(inner)) => content_kind(handler, inner),
+                (_, None) => ResponseKind::Json,
+            }
+        }
+        _ => ResponseKind::Json,
+    }
+}
+
+/// Classifies a stream by the name of its type or macro, i.e. `EventStream`.
+fn stream_kind(handler: &RocketReqHandler, name: &str) -> ResponseKind {
+    match name {
+        "EventStream" => ResponseKind::EventStream(
+            handler
+                .event
+                .as_deref()
+                .and_then(|event| syn::parse_str(event).ok())
+                .map(Box::new),
+        ),
+        "TextStream" => ResponseKind::TextStream,
+        "ByteStream" | "ReaderStream" => ResponseKind::ByteStream,
+        _ => ResponseKind::Json,
+    }
+}
+
+/// Returns true if the handler takes a `rocket_ws` websocket.
+fn takes_websocket(handler: &RocketReqHandler) -> bool {
+    handler.params.iter().any(|param| match param {
+        FnArg::Typed(pat_type) => match &*pat_type.ty {
+            Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "WebSocket"),
+            _ => false,
+        },
+        FnArg::Receiver(_) => false,
+    })
+}
+
+/// Classifies the response of a request handler.
+///
+/// i.e. `Result<Json<Thread>, Status>` -> `ResponseKind::Json`, `Option<NamedFile>` ->
+/// `ResponseKind::Binary`, `EventStream![]` -> `ResponseKind::EventStream`
+pub fn response_kind(handler: &RocketReqHandler) -> ResponseKind {
+    if takes_websocket(handler) {
+        return ResponseKind::WebSocket;
+    }
+
+    match &handler.return_type {
+        ReturnType::Type(_, ty) => content_kind(handler, ty),
+        ReturnType::Default => ResponseKind::Empty,
+    }
+}
@@ -0,0 +1,86 @@
+use syn::{GenericArgument, PathArguments, ReturnType, Type};
+
+use crate::parser::type_args::first_type_arg;
+
+/// Returns the value a responder serializes, looking through references, `(Status, R)` tuples,
+/// `Option`, `Result` and status wrappers like `status::Custom` the way `content_kind` does, and
+/// removing the `Json` wrapper.
+///
+/// Unknown responders with type arguments are assumed to wrap their first argument, e.g.
+/// `K7Response<T>`. Inside of one, only `Json` is removed, as the value may be an `Option` or
+/// `Result` itself.
+///
+/// # Arguments
+///
+/// * `ty` - The responder type.
+/// * `wrapped` - True inside of an unknown responder.
+fn value_type(ty: &Type, wrapped: bool) -> Option<&Type> {
+    match ty {
+        Type::Reference(reference) => value_type(&reference.elem, wrapped),
+        Type::Paren(paren) => value_type(&paren.elem, wrapped),
+        Type::Group(group) => value_type(&group.elem, wrapped),
+        Type::Tuple(tuple) if !wrapped => match tuple.elems.last() {
+            Some(last) => value_type(last, wrapped),
+            None => Some(ty),
+        },
+        Type::Path(type_path) => {
+            let ident = type_path.path.segments.last()?.ident.to_string();
+            match (ident.as_str(), first_type_arg(ty)) {
+                ("Json", Some(inner)) => Some(inner),
+                (_, _) if wrapped => Some(ty),
+                (
+                    "Option" | "Result" | "Box" | "Custom" | "Created" | "Accepted" | "BadRequest"
+                    | "Unauthorized" | "Forbidden" | "NotFound" | "Conflict",
+                    Some(inner),
+                ) => value_type(inner, wrapped),
+                ("Vec" | "VecDeque" | "HashMap" | "BTreeMap" | "HashSet" | "BTreeSet", _) => {
+                    Some(ty)
+                }
+                (_, Some(inner)) => value_type(inner, true),
+                (_, None) => Some(ty),
+            }
+        }
+        _ => Some(ty),
+    }
+}
+
+/// Provide the Rust type of the value a request handler serializes into its response.
+///
+/// Keeps the `syn::Type` so callers can walk the value's structure, see `value_type`.
+///
+/// i.e. Result<Json<Message>, Error> -> Message, K7Response<Vec<Comment>> -> Vec<Comment>,
+/// (Status, Json<Thread>) -> Thread, Result<Option<Json<Thread>>, Status> -> Thread
+pub fn response_type(node: &ReturnType) -> Option<Type> {
+    let ReturnType::Type(_, ty) = node else {
+        return None;
+    };
+
+    value_type(ty, false).cloned()
+}
+
+/// Provide the Rust type of the error a request handler may respond with.
+///
+/// i.e. Result<Json<Message>, ApiError> -> ApiError, Json<Message> -> None
+pub fn error_type(node: &ReturnType) -> Option<Type> {
+    let ReturnType::Type(_, ty) = node else {
+        return None;
+    };
+    let Type::Path(type_path) = &**ty else {
+        return None;
+    };
+    let last = type_path.path.segments.last()?;
+    if last.ident != "Result" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+
+    args.args
+        .iter()
+        .filter_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None,
+        })
+        .nth(1)
+}
@@ -0,0 +1,94 @@
+use syn::{Attribute, Lit, Meta, NestedMeta};
+
+/// The prefix of a doc-comment line carrying rocket-ts options, i.e. `/// @rocket-ts skip`.
+pub const DOC_DIRECTIVE: &str = "@rocket-ts";
+
+/// Generation options declared on a handler with the inert `#[rocket_ts(...)]` attribute or a
+/// `/// @rocket-ts ...` doc-comment directive.
+///
+/// i.e. `#[rocket_ts(rename = "loadThread")]`, `/// @rocket-ts returns = "Thread[]"`
+#[derive(Debug, Default)]
+pub struct RocketTsAttr {
+    /// Leave the item out of the generated output.
+    pub skip: bool,
+    /// Name to emit instead of the function name.
+    pub rename: Option<String>,
+    /// TypeScript type to emit instead of the one derived from the return type.
+    pub returns: Option<String>,
+    /// Rust type of the JSON payload of the events an `EventStream![]` handler sends.
+    pub event: Option<String>,
+}
+
+/// Returns the options of `#[rocket_ts(...)]` and `#[cfg_attr(rocket_ts, rocket_ts(...))]`.
+///
+/// The `cfg_attr` form lets the compiler strip the attribute, so the backend builds without a
+/// proc-macro providing it.
+fn rocket_ts_options(attr: &Attribute) -> Vec<NestedMeta> {
+    match attr.parse_meta() {
+        Ok(Meta::List(list)) if list.path.is_ident("rocket_ts") => {
+            list.nested.into_iter().collect()
+        }
+        Ok(Meta::List(list)) if list.path.is_ident("cfg_attr") => list
+            .nested
+            .into_iter()
+            .skip(1)
+            .filter_map(|nested| match nested {
+                NestedMeta::Meta(Meta::List(inner)) if inner.path.is_ident("rocket_ts") => {
+                    Some(inner.nested)
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect(),
+        Ok(Meta::NameValue(name_value)) if name_value.path.is_ident("doc") => {
+            let Lit::Str(lit) = name_value.lit else {
+                return vec![];
+            };
+            lit.value()
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix(DOC_DIRECTIVE))
+                .filter_map(|directive| {
+                    syn::parse_str::<Meta>(&format!("rocket_ts({directive})")).ok()
+                })
+                .filter_map(|meta| match meta {
+                    Meta::List(list) => Some(list.nested),
+                    _ => None,
+                })
+                .flatten()
+                .collect()
+        }
+        _ => vec![],
+    }
+}
+
+/// Collects the rocket-ts options from a list of attributes.
+///
+/// Unknown options are ignored, so newer annotations don't break older versions of rocket-ts.
+pub fn rocket_ts_attr(attrs: &[Attribute]) -> RocketTsAttr {
+    let mut options = RocketTsAttr::default();
+
+    for attr in attrs {
+        for nested in rocket_ts_options(attr) {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+                    options.skip = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    let Lit::Str(lit) = name_value.lit else {
+                        continue;
+                    };
+                    if name_value.path.is_ident("rename") {
+                        options.rename = Some(lit.value());
+                    } else if name_value.path.is_ident("returns") {
+                        options.returns = Some(lit.value());
+                    } else if name_value.path.is_ident("event") {
+                        options.event = Some(lit.value());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    options
+}
@@ -0,0 +1,77 @@
+use syn::{Attribute, Lit, Meta, NestedMeta};
+
+/// The `#[serde(...)]` options rocket-ts interprets on containers, fields and variants.
+#[derive(Debug, Default)]
+pub struct SerdeAttrs {
+    /// `rename = "..."`, or the `serialize` name of `rename(serialize = "...")`.
+    pub rename: Option<String>,
+    /// `rename_all = "..."`, or the `serialize` rule of `rename_all(serialize = "...")`.
+    pub rename_all: Option<String>,
+    /// `skip` or `skip_serializing`.
+    pub skip: bool,
+    /// `skip_serializing_if = "..."`, making the field optional on the wire.
+    pub skip_serializing_if: bool,
+    /// `tag = "..."` of an internally or adjacently tagged enum.
+    pub tag: Option<String>,
+    /// `content = "..."` of an adjacently tagged enum.
+    pub content: Option<String>,
+    /// `untagged` on an enum.
+    pub untagged: bool,
+}
+
+/// Returns the string value of `name = "..."`, or of `serialize` in `name(serialize = "...")`.
+fn serialize_name(meta: &Meta) -> Option<String> {
+    match meta {
+        Meta::NameValue(name_value) => match &name_value.lit {
+            Lit::Str(lit) => Some(lit.value()),
+            _ => None,
+        },
+        Meta::List(list) => list.nested.iter().find_map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(name_value))
+                if name_value.path.is_ident("serialize") =>
+            {
+                match &name_value.lit {
+                    Lit::Str(lit) => Some(lit.value()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }),
+        Meta::Path(_) => None,
+    }
+}
+
+/// Collects the `#[serde(...)]` options from a list of attributes.
+pub fn serde_attrs(attrs: &[Attribute]) -> SerdeAttrs {
+    let mut serde = SerdeAttrs::default();
+
+    for attr in attrs.iter().filter(|a| a.path.is_ident("serde")) {
+        let Ok(Meta::List(list)) = attr.parse_meta() else {
+            continue;
+        };
+
+        for nested in list.nested {
+            let NestedMeta::Meta(meta) = nested else {
+                continue;
+            };
+
+            if meta.path().is_ident("rename") {
+                serde.rename = serialize_name(&meta);
+            } else if meta.path().is_ident("rename_all") {
+                serde.rename_all = serialize_name(&meta);
+            } else if meta.path().is_ident("skip") || meta.path().is_ident("skip_serializing") {
+                serde.skip = true;
+            } else if meta.path().is_ident("skip_serializing_if") {
+                serde.skip_serializing_if = true;
+            } else if meta.path().is_ident("tag") {
+                serde.tag = serialize_name(&meta);
+            } else if meta.path().is_ident("content") {
+                serde.content = serialize_name(&meta);
+            } else if meta.path().is_ident("untagged") {
+                serde.untagged = true;
+            }
+        }
+    }
+
+    serde
+}
@@ -0,0 +1,92 @@
+use std::path::{Path, PathBuf};
+
+use crate::parser::model::RustModel;
+use crate::parser::visitor::RocketReqHandler;
+
+/// The handlers and models extracted from a single source file.
+pub struct SourceModule {
+    /// The Rust module name of the file, e.g. `thread` for `thread.rs`.
+    pub name: String,
+    /// The file name, e.g. `thread.rs`.
+    pub file_name: String,
+    /// The path of the file, as given on the command line.
+    pub path: PathBuf,
+    /// The Rocket request handlers declared in the file.
+    pub handlers: Vec<RocketReqHandler>,
+    /// The handlers compiled out by their `#[cfg(...)]` predicates.
+    pub gated_handlers: Vec<RocketReqHandler>,
+    /// The serializable models declared in the file.
+    pub models: Vec<RustModel>,
+}
+
+/// Derives the Rust module name of a source file.
+///
+/// The root of a workspace crate is named after the crate, as every crate root would otherwise
+/// be named `src`.
+///
+/// i.e. `routes/thread.rs` -> "thread", `routes/admin/mod.rs` -> "admin", `api/src/lib.rs` of
+/// the crate `api` -> "api"
+///
+/// # Arguments
+///
+/// * `file_path` - The path of the source file.
+/// * `crate_name` - The name of the workspace crate the file belongs to, or `crate` for inputs
+///   given with `--input`.
+pub fn module_name(file_path: &Path, crate_name: &str) -> String {
+    let stem = file_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+
+    let parent = file_path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|p| p.to_str());
+    if (stem == "lib" || stem == "main") && parent == Some("src") && crate_name != "crate" {
+        return crate_name.to_string();
+    }
+
+    if stem == "mod" || stem == "lib" || stem == "main" {
+        if let Some(parent) = parent {
+            return parent.to_string();
+        }
+    }
+
+    stem.to_string()
+}
+
+/// Derives the Rust module path of a source file.
+///
+/// Paths below a `src` directory are resolved from the crate root, other files are treated as
+/// top-level modules.
+///
+/// i.e. `api/src/routes/thread.rs` -> ["crate", "routes", "thread"], `api/src/lib.rs` ->
+/// ["crate"], `handlers/thread.rs` -> ["thread"]
+///
+/// # Arguments
+///
+/// * `file_path` - The path of the source file.
+/// * `crate_name` - The name of the crate root, i.e. `crate`, or the name of a workspace crate
+///   so paths into it from other crates resolve, see `SymbolTable`.
+pub fn module_path(file_path: &Path, crate_name: &str) -> Vec<String> {
+    let components: Vec<&str> = file_path
+        .iter()
+        .filter_map(|component| component.to_str())
+        .collect();
+
+    let Some(src) = components.iter().rposition(|c| *c == "src") else {
+        return vec![module_name(file_path, crate_name)];
+    };
+
+    let mut path = vec![crate_name.to_string()];
+    let rest = &components[src + 1..];
+    if let Some((file, dirs)) = rest.split_last() {
+        path.extend(dirs.iter().map(|dir| dir.to_string()));
+        let stem = file.strip_suffix(".rs").unwrap_or(file);
+        if stem != "mod" && stem != "lib" && stem != "main" {
+            path.push(stem.to_string());
+        }
+    }
+
+    path
+}
@@ -0,0 +1,156 @@
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::parser::workspace::source_files;
+
+/// Where the input files are read from.
+pub enum SourceTree {
+    /// The files on disk.
+    WorkingTree,
+    /// The files of a git revision, read from the local object store without checking it out.
+    Revision(String),
+}
+
+/// Runs git in a directory, returning its standard output.
+fn git(dir: &Path, args: &[&str]) -> std::io::Result<String> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output()?;
+    if !output.status.success() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!(
+                "git {}: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ));
+    }
+
+    String::from_utf8(output.stdout).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+}
+
+/// Splits a path into the directory git runs in and the object name relative to it.
+///
+/// Git runs in the nearest ancestor existing on disk, as directories of the revision may have
+/// been moved or removed since.
+///
+/// i.e. `routes/thread.rs` -> (`routes`, `./thread.rs`), `routes` -> (`.`, `./routes`),
+/// `removed/api/Cargo.toml` -> (`.`, `./removed/api/Cargo.toml`)
+fn locate(path: &Path) -> (PathBuf, String) {
+    let mut dir = path.parent();
+    while let Some(parent) = dir {
+        if parent.as_os_str().is_empty() || parent.is_dir() {
+            break;
+        }
+        dir = parent.parent();
+    }
+    let dir = match dir {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let name = match path.strip_prefix(&dir) {
+        Ok(rest) if !rest.as_os_str().is_empty() => rest.to_string_lossy().to_string(),
+        _ => path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| String::from(".")),
+    };
+    (dir, format!("./{name}"))
+}
+
+impl SourceTree {
+    /// Selects the files of a git revision, i.e. a branch, tag or commit, after checking it
+    /// exists.
+    pub fn revision(rev: &str) -> std::io::Result<SourceTree> {
+        git(
+            Path::new("."),
+            &[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{rev}^{{commit}}"),
+            ],
+        )
+        .map_err(|_| Error::new(ErrorKind::NotFound, format!("unknown revision `{rev}`")))?;
+        Ok(SourceTree::Revision(rev.to_string()))
+    }
+
+    /// Returns the git object type of a path in the revision, i.e. `blob` or `tree`.
+    fn object_type(rev: &str, path: &Path) -> Option<String> {
+        let (dir, name) = locate(path);
+        git(&dir, &["cat-file", "-t", &format!("{rev}:{name}")])
+            .ok()
+            .map(|kind| kind.trim().to_string())
+    }
+
+    /// Returns the paths in a directory of the revision, recursively or only its entries.
+    fn ls_tree(rev: &str, dir: &Path, recursive: bool) -> std::io::Result<Vec<PathBuf>> {
+        let (parent, name) = locate(dir);
+        let tree = format!("{rev}:{name}");
+        let mut args = vec!["ls-tree", "-z", "--full-tree", "--name-only"];
+        if recursive {
+            args.push("-r");
+        }
+        args.push(&tree);
+
+        Ok(git(&parent, &args)?
+            .split('\0')
+            .filter(|name| !name.is_empty())
+            .map(|name| dir.join(name))
+            .collect())
+    }
+
+    /// Returns true if the path is a file.
+    pub fn is_file(&self, path: &Path) -> bool {
+        match self {
+            SourceTree::WorkingTree => path.is_file(),
+            SourceTree::Revision(rev) => Self::object_type(rev, path).as_deref() == Some("blob"),
+        }
+    }
+
+    /// Returns true if the path is a directory.
+    pub fn is_dir(&self, path: &Path) -> bool {
+        match self {
+            SourceTree::WorkingTree => path.is_dir(),
+            SourceTree::Revision(rev) => Self::object_type(rev, path).as_deref() == Some("tree"),
+        }
+    }
+
+    /// Returns the paths of the entries of a directory, without descending into it.
+    pub fn entries(&self, dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+        match self {
+            SourceTree::WorkingTree => fs::read_dir(dir)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect(),
+            SourceTree::Revision(rev) => Self::ls_tree(rev, dir, false),
+        }
+    }
+
+    /// Collects the Rust source files below a directory, recursively.
+    pub fn source_files(&self, dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+        match self {
+            SourceTree::WorkingTree => source_files(dir, files),
+            SourceTree::Revision(rev) => {
+                files.extend(
+                    Self::ls_tree(rev, dir, true)?
+                        .into_iter()
+                        .filter(|path| path.extension().is_some_and(|ext| ext == "rs")),
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Reads the contents of a file.
+    pub fn read_to_string(&self, path: &Path) -> std::io::Result<String> {
+        match self {
+            SourceTree::WorkingTree => fs::read_to_string(path),
+            SourceTree::Revision(rev) => {
+                let (dir, name) = locate(path);
+                git(&dir, &["show", &format!("{rev}:{name}")])
+            }
+        }
+    }
+}
@@ -0,0 +1,434 @@
+use std::collections::HashMap;
+
+use log::debug;
+use serde::{Deserialize, Serialize};
+use syn::{
+    visit_mut::{self, VisitMut},
+    FnArg, GenericArgument, Path, PathArguments, PathSegment, ReturnType, Type, UseTree,
+};
+
+use crate::parser::model::{ModelKind, VariantFields};
+use crate::parser::source_module::SourceModule;
+use crate::parser::token_serde::tokens;
+
+/// How deep aliases referring to other aliases and chains of re-exports are followed, so cyclic
+/// declarations terminate.
+const MAX_DEPTH: usize = 16;
+
+/// A `type` alias, i.e. `type ApiResult<T> = Result<Json<T>, ApiError>;`.
+#[derive(Serialize, Deserialize)]
+pub struct TypeAlias {
+    /// The module path of the alias followed by its name.
+    pub path: Vec<String>,
+    /// The names of the alias's type parameters, i.e. `["T"]`.
+    pub generics: Vec<String>,
+    /// The aliased type.
+    #[serde(with = "tokens")]
+    pub ty: Type,
+    /// The path of the module declaring the alias, which the aliased type is resolved in.
+    pub module_path: Vec<String>,
+    /// The `#[cfg(...)]` predicates of the alias and its enclosing modules.
+    pub cfg: Vec<String>,
+}
+
+/// A name brought into a module's scope with `use`.
+#[derive(Serialize, Deserialize)]
+pub struct UseImport {
+    /// The path of the module containing the `use` declaration.
+    pub scope: Vec<String>,
+    /// The name in scope, i.e. `Topic` for `use crate::model::Thread as Topic;`, or None for a
+    /// glob import.
+    pub name: Option<String>,
+    /// The imported path as written, i.e. `["crate", "model", "Thread"]`, or the module path of
+    /// a glob import.
+    pub target: Vec<String>,
+    /// The `#[cfg(...)]` predicates of the declaration and its enclosing modules.
+    pub cfg: Vec<String>,
+}
+
+/// Flattens a `use` tree into the names it brings into scope.
+///
+/// i.e. `use crate::model::{Thread as Topic, *};` -> [(Some("Topic"), ["crate", "model", "Thread"]),
+/// (None, ["crate", "model"])]
+///
+/// # Arguments
+///
+/// * `tree` - The tree to flatten.
+/// * `prefix` - The path leading up to the tree.
+/// * `imports` - Receives the name and target of each import.
+pub fn use_imports(
+    tree: &UseTree,
+    mut prefix: Vec<String>,
+    imports: &mut Vec<(Option<String>, Vec<String>)>,
+) {
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.to_string());
+            use_imports(&path.tree, prefix, imports);
+        }
+        UseTree::Name(name) if name.ident == "self" => {
+            if let Some(last) = prefix.last().cloned() {
+                imports.push((Some(last), prefix));
+            }
+        }
+        UseTree::Name(name) => {
+            prefix.push(name.ident.to_string());
+            imports.push((Some(name.ident.to_string()), prefix));
+        }
+        UseTree::Rename(rename) if rename.rename == "_" => {}
+        UseTree::Rename(rename) => {
+            if rename.ident != "self" {
+                prefix.push(rename.ident.to_string());
+            }
+            imports.push((Some(rename.rename.to_string()), prefix));
+        }
+        UseTree::Glob(_) => imports.push((None, prefix)),
+        UseTree::Group(group) => {
+            for item in &group.items {
+                use_imports(item, prefix.clone(), imports);
+            }
+        }
+    }
+}
+
+/// The type aliases and imports of all scanned files, used to resolve the types of handlers and
+/// models to their canonical form.
+///
+/// A canonical type has every alias expanded and every path reduced to the name of the item it
+/// refers to, i.e. `ApiResult<Topic>` -> `Result<Json<Thread>, ApiError>` given
+/// `type ApiResult<T> = Result<Json<T>, ApiError>;` and `use crate::model::Thread as Topic;`.
+#[derive(Default)]
+pub struct SymbolTable {
+    aliases: HashMap<Vec<String>, TypeAlias>,
+    imports: Vec<UseImport>,
+    /// The names of the workspace crates, whose module paths start with their name rather than
+    /// `crate`, see `module_path`.
+    crates: Vec<String>,
+}
+
+/// The route attributes of Rocket, i.e. `rocket::get`.
+const ROUTE_METHODS: [&str; 7] = ["get", "put", "post", "delete", "head", "patch", "options"];
+
+impl SymbolTable {
+    /// Creates a table resolving paths across the given workspace crates, i.e. `domain::Thread`
+    /// written in another crate of the workspace.
+    pub fn new(crates: Vec<String>) -> SymbolTable {
+        SymbolTable {
+            crates,
+            ..SymbolTable::default()
+        }
+    }
+
+    /// Returns the module path of the crate root for a module, see `module_path`.
+    fn crate_root(&self, scope: &[String]) -> Vec<String> {
+        match scope.first() {
+            Some(first) if first == "crate" || self.crates.contains(first) => vec![first.clone()],
+            _ => vec![],
+        }
+    }
+
+    /// Adds the aliases and imports collected from a file.
+    pub fn extend(&mut self, aliases: Vec<TypeAlias>, imports: Vec<UseImport>) {
+        for alias in aliases {
+            self.aliases.insert(alias.path.clone(), alias);
+        }
+        self.imports.extend(imports);
+    }
+
+    /// Returns the import declaring a name in a module.
+    fn import(&self, scope: &[String], name: &str) -> Option<&UseImport> {
+        self.imports
+            .iter()
+            .find(|import| import.scope == scope && import.name.as_deref() == Some(name))
+    }
+
+    /// Returns true if a module declares or imports an item with the given name.
+    fn declares(&self, scope: &[String], name: &str) -> bool {
+        let mut path = scope.to_vec();
+        path.push(name.to_string());
+        self.aliases.contains_key(&path) || self.import(scope, name).is_some()
+    }
+
+    /// Resolves a path as written in a module to the path of the item it refers to.
+    ///
+    /// Paths into other crates and items the table knows nothing about are returned as written.
+    fn resolve(&self, scope: &[String], segments: &[String], depth: usize) -> Vec<String> {
+        let Some(first) = segments.first() else {
+            return vec![];
+        };
+        if depth > MAX_DEPTH {
+            return segments.to_vec();
+        }
+
+        if first == "crate" || first == "self" || first == "super" {
+            let mut base = scope.to_vec();
+            let mut rest = segments;
+            if first == "crate" {
+                base = self.crate_root(scope);
+                rest = &rest[1..];
+            }
+            while let Some(segment) = rest.first() {
+                match segment.as_str() {
+                    "self" => {}
+                    "super" => {
+                        base.pop();
+                    }
+                    _ => break,
+                }
+                rest = &rest[1..];
+            }
+            base.extend(rest.iter().cloned());
+            return self.reexported(base, depth);
+        }
+
+        if self.crates.contains(first) {
+            return self.reexported(segments.to_vec(), depth);
+        }
+
+        if let Some(import) = self.import(scope, first) {
+            let mut target = import.target.clone();
+            target.extend(segments[1..].iter().cloned());
+            return self.resolve(&import.scope, &target, depth + 1);
+        }
+
+        let mut local = scope.to_vec();
+        local.extend(segments.iter().cloned());
+        if self.aliases.contains_key(&local) {
+            return local;
+        }
+
+        for glob in self
+            .imports
+            .iter()
+            .filter(|i| i.scope == scope && i.name.is_none())
+        {
+            let module = self.resolve(&glob.scope, &glob.target, depth + 1);
+            if self.declares(&module, first) {
+                return self.resolve(&module, segments, depth + 1);
+            }
+        }
+
+        segments.to_vec()
+    }
+
+    /// Resolves the path of a route attribute as written in a module to the HTTP method of the
+    /// Rocket route attribute it refers to.
+    ///
+    /// i.e. `rocket::get`, or `GET` given `use rocket::get as GET;` -> "get"
+    ///
+    /// A bare method name that isn't imported, i.e. `get`, is assumed to be Rocket's, made
+    /// available crate-wide with `#[macro_use] extern crate rocket;`.
+    ///
+    /// # Returns
+    ///
+    /// None if the attribute refers to anything other than a Rocket route attribute, i.e.
+    /// `get` given `use actix_web::get;`.
+    pub fn route_method(&self, scope: &[String], attribute: &[String]) -> Option<String> {
+        let resolved = self.resolve(scope, attribute, 0);
+        match resolved.as_slice() {
+            [krate, method] if krate == "rocket" => ROUTE_METHODS
+                .contains(&method.as_str())
+                .then(|| method.clone()),
+            [method] if resolved == attribute => ROUTE_METHODS
+                .contains(&method.as_str())
+                .then(|| method.clone()),
+            _ => None,
+        }
+    }
+
+    /// Sets the HTTP method of a module's handlers from their route attribute, dropping the
+    /// handlers whose attribute isn't a Rocket route attribute, see `route_method`.
+    pub fn resolve_routes(&self, module: &mut SourceModule) {
+        for handlers in [&mut module.handlers, &mut module.gated_handlers] {
+            handlers.retain_mut(|handler| {
+                match self.route_method(&handler.module_path, &handler.attribute) {
+                    Some(method) => {
+                        handler.method = method;
+                        true
+                    }
+                    None => {
+                        debug!(
+                            "Ignoring {}, #[{}] is not a Rocket route attribute",
+                            handler.rust_path,
+                            handler.attribute.join("::")
+                        );
+                        false
+                    }
+                }
+            });
+        }
+    }
+
+    /// Follows a re-export of the item at an absolute path, i.e. `crate::prelude::Thread` given
+    /// `pub use crate::model::Thread;` in `prelude`.
+    fn reexported(&self, path: Vec<String>, depth: usize) -> Vec<String> {
+        let Some((name, module)) = path.split_last() else {
+            return path;
+        };
+        match self.import(module, name) {
+            Some(import) => self.resolve(&import.scope, &import.target, depth + 1),
+            None => path,
+        }
+    }
+
+    /// Resolves a type as written in a module to its canonical form.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The path of the module the type is written in.
+    /// * `ty` - The type, rewritten in place.
+    pub fn canonicalize(&self, scope: &[String], ty: &mut Type) {
+        Canonicalizer {
+            table: self,
+            scope,
+            depth: 0,
+        }
+        .visit_type_mut(ty);
+    }
+
+    /// Resolves the parameter, return and field types of a module's handlers and models to their
+    /// canonical form.
+    pub fn canonicalize_module(&self, module: &mut SourceModule) {
+        for handler in module
+            .handlers
+            .iter_mut()
+            .chain(module.gated_handlers.iter_mut())
+        {
+            for param in handler.params.iter_mut() {
+                if let FnArg::Typed(pat_type) = param {
+                    self.canonicalize(&handler.module_path, &mut pat_type.ty);
+                }
+            }
+            if let ReturnType::Type(_, ty) = &mut handler.return_type {
+                self.canonicalize(&handler.module_path, ty);
+            }
+        }
+
+        for model in module.models.iter_mut() {
+            let scope = &model.module_path;
+            match &mut model.kind {
+                ModelKind::Struct(fields) => {
+                    for field in fields {
+                        self.canonicalize(scope, &mut field.ty);
+                    }
+                }
+                ModelKind::Newtype(ty) => self.canonicalize(scope, ty),
+                ModelKind::NumericEnum(_) => {}
+                ModelKind::Enum(variants, _) => {
+                    for variant in variants {
+                        match &mut variant.fields {
+                            VariantFields::Unit => {}
+                            VariantFields::Tuple(types) => {
+                                for ty in types {
+                                    self.canonicalize(scope, ty);
+                                }
+                            }
+                            VariantFields::Named(fields) => {
+                                for field in fields {
+                                    self.canonicalize(scope, &mut field.ty);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Rewrites types to their canonical form, innermost types first.
+struct Canonicalizer<'a> {
+    table: &'a SymbolTable,
+    scope: &'a [String],
+    depth: usize,
+}
+
+impl VisitMut for Canonicalizer<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        visit_mut::visit_type_mut(self, ty);
+
+        let Type::Path(type_path) = ty else {
+            return;
+        };
+        if type_path.qself.is_some() {
+            return;
+        }
+        let Some(last) = type_path.path.segments.last().cloned() else {
+            return;
+        };
+
+        let segments: Vec<String> = type_path
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        let resolved = self.table.resolve(self.scope, &segments, 0);
+
+        if let Some(alias) = self.table.aliases.get(&resolved) {
+            if self.depth < MAX_DEPTH {
+                let mut expanded = alias.ty.clone();
+                Canonicalizer {
+                    table: self.table,
+                    scope: &alias.module_path,
+                    depth: self.depth + 1,
+                }
+                .visit_type_mut(&mut expanded);
+
+                let args = match &last.arguments {
+                    PathArguments::AngleBracketed(args) => args
+                        .args
+                        .iter()
+                        .filter_map(|arg| match arg {
+                            GenericArgument::Type(ty) => Some(ty.clone()),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => vec![],
+                };
+                Substitution {
+                    params: &alias.generics,
+                    args,
+                }
+                .visit_type_mut(&mut expanded);
+
+                *ty = expanded;
+                return;
+            }
+        }
+
+        let mut segment = PathSegment::from(last.ident.clone());
+        if let Some(name) = resolved.last() {
+            if let Ok(ident) = syn::parse_str(name) {
+                segment.ident = ident;
+            }
+        }
+        segment.arguments = last.arguments;
+        type_path.path = Path::from(segment);
+    }
+}
+
+/// Replaces the type parameters of an alias or generic model with the arguments it was used with.
+pub struct Substitution<'a> {
+    /// The names of the type parameters.
+    pub params: &'a [String],
+    /// The type arguments, in the order of `params`.
+    pub args: Vec<Type>,
+}
+
+impl VisitMut for Substitution<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Type::Path(type_path) = ty {
+            if let Some(ident) = type_path.path.get_ident() {
+                if let Some(i) = self.params.iter().position(|p| ident == p) {
+                    if let Some(arg) = self.args.get(i) {
+                        *ty = arg.clone();
+                        return;
+                    }
+                }
+            }
+        }
+
+        visit_mut::visit_type_mut(self, ty);
+    }
+}
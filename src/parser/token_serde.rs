@@ -0,0 +1,67 @@
+use quote::ToTokens;
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+use syn::parse::Parse;
+
+/// Parses a node back from its source text.
+fn parse<'de, T: Parse, D: Deserializer<'de>>(source: &str) -> Result<T, D::Error> {
+    syn::parse_str(source).map_err(|err| D::Error::custom(format!("{err}: {source}")))
+}
+
+/// Serde adapter storing a syntax tree node as its source text, so extracted handlers and models
+/// can be cached on disk and parsed back.
+///
+/// i.e. `#[serde(with = "tokens")] ty: Type` is stored as "Result < Json < Thread > , Status >"
+pub mod tokens {
+    use super::*;
+
+    pub fn serialize<T: ToTokens, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_token_stream().to_string())
+    }
+
+    pub fn deserialize<'de, T: Parse, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        parse::<T, D>(&String::deserialize(deserializer)?)
+    }
+}
+
+/// Like `tokens`, for a boxed node, i.e. `Box<Type>`.
+pub mod boxed_tokens {
+    use super::*;
+
+    pub use super::tokens::serialize;
+
+    pub fn deserialize<'de, T: Parse, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Box<T>, D::Error> {
+        super::tokens::deserialize(deserializer).map(Box::new)
+    }
+}
+
+/// Like `tokens`, for a list of nodes, i.e. `Vec<FnArg>`.
+pub mod token_vec {
+    use super::*;
+
+    pub fn serialize<T: ToTokens, S: Serializer>(
+        values: &[T],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            values
+                .iter()
+                .map(|value| value.to_token_stream().to_string()),
+        )
+    }
+
+    pub fn deserialize<'de, T: Parse, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<T>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|source| parse::<T, D>(source))
+            .collect()
+    }
+}
@@ -0,0 +1,64 @@
+use syn::Type;
+
+use crate::parser::type_args::type_args;
+
+/// Wraps union types in parentheses so they can be suffixed with `[]`.
+pub fn array_of(inner: String) -> String {
+    if inner.contains(' ') {
+        format!("({inner})[]")
+    } else {
+        format!("{inner}[]")
+    }
+}
+
+/// Maps a Rust type used in a model field to its TypeScript counterpart.
+///
+/// i.e. Option<Vec<String>> -> "string[] | null"
+///
+/// Standard library containers and primitives are mapped structurally. Any other path type is
+/// emitted by the ident of its last segment and its mapped type arguments, on the assumption that
+/// it is itself a model, i.e. Paginated<Vec<Comment>> -> "Paginated<Comment[]>".
+pub fn ts_type(ty: &Type) -> String {
+    match ty {
+        Type::Reference(reference) => ts_type(&reference.elem),
+        Type::Paren(paren) => ts_type(&paren.elem),
+        Type::Group(group) => ts_type(&group.elem),
+        Type::Slice(slice) => array_of(ts_type(&slice.elem)),
+        Type::Array(array) => array_of(ts_type(&array.elem)),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => String::from("null"),
+        Type::Tuple(tuple) => {
+            let elems: Vec<String> = tuple.elems.iter().map(ts_type).collect();
+            format!("[{}]", elems.join(", "))
+        }
+        Type::Path(type_path) => {
+            let Some(last) = type_path.path.segments.last() else {
+                return String::from("any");
+            };
+            let args = type_args(last);
+
+            match (last.ident.to_string().as_str(), args.as_slice()) {
+                (
+                    "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "f32" | "f64"
+                    | "isize" | "usize",
+                    _,
+                ) => String::from("number"),
+                ("i128" | "u128", _) => String::from("string"),
+                ("bool", _) => String::from("boolean"),
+                ("String" | "str" | "char" | "PathBuf" | "Path", _) => String::from("string"),
+                ("Option", [inner]) => format!("{} | null", ts_type(inner)),
+                ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [inner]) => array_of(ts_type(inner)),
+                ("HashMap" | "BTreeMap", [key, value]) => {
+                    format!("Record<{}, {}>", ts_type(key), ts_type(value))
+                }
+                ("Box" | "Arc" | "Rc" | "Cow", [.., inner]) => ts_type(inner),
+                ("Value", _) => String::from("any"),
+                (ident, []) => ident.to_string(),
+                (ident, args) => {
+                    let args: Vec<String> = args.iter().map(|arg| ts_type(arg)).collect();
+                    format!("{ident}<{}>", args.join(", "))
+                }
+            }
+        }
+        _ => String::from("any"),
+    }
+}
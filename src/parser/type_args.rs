@@ -0,0 +1,27 @@
+use syn::{GenericArgument, PathArguments, Type};
+
+/// Returns the type arguments of a path segment, e.g. `[K, V]` for `HashMap<K, V>`.
+pub fn type_args(segment: &syn::PathSegment) -> Vec<&Type> {
+    match &segment.arguments {
+        PathArguments::AngleBracketed(params) => params
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Returns the first type argument of a path type, i.e. `T` for `Json<T>` or `Result<T, E>`.
+pub fn first_type_arg(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+
+    type_args(type_path.path.segments.last()?)
+        .into_iter()
+        .next()
+}
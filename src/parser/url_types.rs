@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+use syn::Type;
+
+use crate::parser::ts_type::{array_of, ts_type};
+use crate::parser::type_args::first_type_arg;
+
+/// Parses a `--url-type` override, i.e. `Kid=number` -> ("Kid", "number").
+pub fn parse_url_type(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, ts)) if !name.trim().is_empty() && !ts.trim().is_empty() => {
+            Ok((name.trim().to_string(), ts.trim().to_string()))
+        }
+        _ => Err(format!("expected TYPE=TS_TYPE, got `{arg}`")),
+    }
+}
+
+/// Maps the Rust type of a path segment or query value to the TypeScript type callers send.
+///
+/// i.e. Option<Kid> -> "string | null" given `impl<'a> FromParam<'a> for Kid`
+///
+/// # Arguments
+///
+/// * `ty` - The type of the handler parameter.
+/// * `url_types` - The TypeScript types of custom URL value types, see `GeneratorOptions`.
+pub fn url_ts_type(ty: &Type, url_types: &BTreeMap<String, String>) -> String {
+    match ty {
+        Type::Reference(reference) => url_ts_type(&reference.elem, url_types),
+        Type::Paren(paren) => url_ts_type(&paren.elem, url_types),
+        Type::Group(group) => url_ts_type(&group.elem, url_types),
+        Type::Path(type_path) => {
+            let Some(last) = type_path.path.segments.last() else {
+                return ts_type(ty);
+            };
+            let ident = last.ident.to_string();
+            if let Some(ts) = url_types.get(&ident) {
+                return ts.clone();
+            }
+
+            match (ident.as_str(), first_type_arg(ty)) {
+                ("Option", Some(inner)) => format!("{} | null", url_ts_type(inner, url_types)),
+                ("Result", Some(inner)) => url_ts_type(inner, url_types),
+                ("Vec", Some(inner)) => array_of(url_ts_type(inner, url_types)),
+                _ => ts_type(ty),
+            }
+        }
+        _ => ts_type(ty),
+    }
+}
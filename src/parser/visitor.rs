@@ -1,30 +1,206 @@
+use log::debug;
+use serde::{Deserialize, Serialize};
 use syn::{
     visit::{self, Visit},
-    Attribute, FnArg, ItemFn, ReturnType,
+    Attribute, File, FnArg, ImplItemMethod, ItemEnum, ItemFn, ItemImpl, ItemMod, ItemStruct,
+    ItemType, ItemUse, Lit, Meta, NestedMeta, ReturnType, Signature, Type,
 };
 
+use crate::parser::cfg_predicate::cfg_predicates;
+use crate::parser::doc_comments::{doc_comments, Docs};
+use crate::parser::model::{derives_serde, derives_url_value, RustModel};
+use crate::parser::rocket_ts_attr::rocket_ts_attr;
+use crate::parser::symbol_table::{use_imports, TypeAlias, UseImport};
+use crate::parser::token_serde::{token_vec, tokens};
+
 /// Represents a Rocket request handler.
 ///
 /// This struct holds information about a Rocket request handler, including its name,
-/// path, parameters, and return type.
+/// path, parameters, return type and documentation.
+#[derive(Serialize, Deserialize)]
 pub struct RocketReqHandler {
-    /// The name of the request handler function.
+    /// The name to emit for the request handler. The function name, unless overridden with
+    /// `#[rocket_ts(rename = "...")]` or renamed to resolve a collision.
     pub name: String,
+    /// True if the name was given explicitly with `#[rocket_ts(rename = "...")]`.
+    pub explicit_name: bool,
+    /// The HTTP method of the route attribute, e.g. `get`.
+    pub method: String,
+    /// The path of the route attribute as written, i.e. `["rocket", "get"]` for
+    /// `#[rocket::get("/")]`, see `SymbolTable::resolve_routes`.
+    pub attribute: Vec<String>,
     /// The path associated with the request handler.
     pub path: String,
+    /// The parameter bound to the request body, i.e. `escalation` for `data = "<escalation>"`.
+    pub data: Option<String>,
+    /// The line of the function name in its source file.
+    pub line: usize,
     /// The parameters of the request handler function.
+    #[serde(with = "token_vec")]
     pub params: Vec<FnArg>,
     /// The return type of the request handler function.
+    #[serde(with = "tokens")]
     pub return_type: ReturnType,
+    /// The TypeScript type given with `#[rocket_ts(returns = "...")]`, overriding the one derived
+    /// from `return_type`.
+    pub returns: Option<String>,
+    /// The Rust type of the event payload given with `#[rocket_ts(event = "...")]`, i.e.
+    /// `Message` for an `EventStream![]` sending `Event::json(&message)`.
+    pub event: Option<String>,
+    /// Documentation carried over from the handler function.
+    pub docs: Docs,
+    /// The `#[cfg(...)]` predicates of the handler and its enclosing modules.
+    pub cfg: Vec<String>,
+    /// The path of the module declaring the handler, i.e. `["crate", "thread", "admin"]` for a
+    /// handler in an inline `mod admin` of `src/thread.rs`.
+    pub module_path: Vec<String>,
+    /// The fully qualified Rust path of the handler function, i.e. `crate::thread::admin::get_list`
+    /// or `crate::thread::ThreadRoutes::get_list` for an associated function.
+    pub rust_path: String,
+}
+
+/// The `#[cfg(...)]` predicates an out-of-line module declaration, i.e. `mod admin;`, puts on
+/// the file holding the module, see `Visitor::module_cfgs`.
+#[derive(Serialize, Deserialize)]
+pub struct ModuleCfg {
+    /// The path of the declared module, i.e. `["crate", "admin"]` for `mod admin;` in `src/lib.rs`.
+    pub module_path: Vec<String>,
+    /// The predicates of the declaration and its enclosing modules.
+    pub cfg: Vec<String>,
 }
 
 /// Visitor for traversing Rust syntax tree and extracting Rocket request handlers.
 ///
 /// This struct implements the Visit trait to traverse the syntax tree and extract
-/// information about Rocket request handlers.
+/// information about Rocket request handlers and the serializable models next to them.
+#[derive(Default)]
 pub struct Visitor {
     /// Vector to store extracted Rocket request handlers.
     pub functions: Vec<RocketReqHandler>,
+    /// Vector to store extracted structs and enums deriving serde traits.
+    pub models: Vec<RustModel>,
+    /// The `#[cfg(...)]` predicates of the file and the modules currently being visited.
+    pub cfg_stack: Vec<String>,
+    /// The path of the module currently being visited, starting with the file's module path.
+    pub module_path: Vec<String>,
+    /// The self type of the `impl` block currently being visited.
+    pub impl_type: Option<String>,
+    /// Vector to store `type` aliases, see `SymbolTable`.
+    pub aliases: Vec<TypeAlias>,
+    /// Vector to store the names brought into scope with `use`.
+    pub imports: Vec<UseImport>,
+    /// Vector to store the types implementing `FromRequest`, i.e. request guards.
+    pub guards: Vec<String>,
+    /// Vector to store the gated `mod x;` declarations, whose predicates apply to the files
+    /// holding the declared modules.
+    pub module_cfgs: Vec<ModuleCfg>,
+    /// Vector to store the types implementing or deriving `FromParam`, `FromSegments` or
+    /// `FromFormField`, which are sent as strings in the URL.
+    pub url_types: Vec<String>,
+}
+
+impl Visitor {
+    /// Creates a visitor for a file with the given module path, see `module_path`.
+    pub fn new(module_path: Vec<String>) -> Visitor {
+        Visitor {
+            module_path,
+            ..Visitor::default()
+        }
+    }
+
+    /// Returns the predicates an item with the given attributes is compiled under.
+    fn item_cfg(&self, attrs: &[Attribute]) -> Vec<String> {
+        let mut cfg = self.cfg_stack.clone();
+        cfg.extend(cfg_predicates(attrs));
+        cfg
+    }
+
+    /// Records a function or associated function as a handler if it carries a route attribute.
+    ///
+    /// Attributes are matched by their shape only, since whether `#[get(...)]` refers to Rocket
+    /// depends on the imports of the scanned files. Handlers whose attribute turns out to be
+    /// something else are dropped by `SymbolTable::resolve_routes`.
+    fn visit_handler(&mut self, attrs: &[Attribute], sig: &Signature) {
+        for attr in attrs {
+            if let Some(last_segment) = attr.path.segments.last() {
+                if is_route_shaped(attr) {
+                    let options = rocket_ts_attr(attrs);
+                    if options.skip {
+                        debug!("Skipping handler {} marked with skip", sig.ident);
+                        continue;
+                    }
+
+                    let explicit_name = options.rename.is_some();
+                    let function_name = options.rename.unwrap_or_else(|| sig.ident.to_string());
+
+                    let path = handle_get_attr(attr);
+
+                    let mut params: Vec<FnArg> = vec![];
+                    for input in &sig.inputs {
+                        params.push((*input).clone());
+                    }
+
+                    let return_type = sig.output.clone();
+
+                    let mut rust_path = self.module_path.clone();
+                    rust_path.extend(self.impl_type.clone());
+                    rust_path.push(sig.ident.to_string());
+
+                    let req_handler = RocketReqHandler {
+                        name: function_name,
+                        explicit_name,
+                        method: last_segment.ident.to_string().to_lowercase(),
+                        attribute: attr
+                            .path
+                            .segments
+                            .iter()
+                            .map(|segment| segment.ident.to_string())
+                            .collect(),
+                        path,
+                        data: handle_data_attr(attr),
+                        line: sig.ident.span().start().line,
+                        params,
+                        return_type,
+                        returns: options.returns,
+                        event: options.event,
+                        docs: doc_comments(attrs),
+                        cfg: self.item_cfg(attrs),
+                        module_path: self.module_path.clone(),
+                        rust_path: rust_path.join("::"),
+                    };
+                    self.functions.push(req_handler);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the names of the trait and the type of a trait `impl` block, looking through
+/// references, i.e. `("FromRequest", "User")` for `impl<'r> FromRequest<'r> for &'r User`.
+pub fn trait_impl(item_impl: &ItemImpl) -> Option<(String, String)> {
+    let (_, trait_path, _) = item_impl.trait_.as_ref()?;
+    let trait_name = trait_path.segments.last()?.ident.to_string();
+
+    let mut self_ty = &*item_impl.self_ty;
+    while let Type::Reference(reference) = self_ty {
+        self_ty = &reference.elem;
+    }
+    match self_ty {
+        Type::Path(type_path) => Some((
+            trait_name,
+            type_path.path.segments.last()?.ident.to_string(),
+        )),
+        _ => None,
+    }
+}
+
+/// Returns true if an attribute has the shape of a route attribute, i.e. a path string followed
+/// by options, as in `#[get("/thread/<kid>", format = "json")]`.
+pub fn is_route_shaped(attr: &Attribute) -> bool {
+    let Ok(Meta::List(list)) = attr.parse_meta() else {
+        return false;
+    };
+    matches!(list.nested.first(), Some(NestedMeta::Lit(Lit::Str(_))))
 }
 
 /// Extracts the path attribute value from a syn::Attribute.
@@ -49,33 +225,162 @@ pub fn handle_get_attr(attr: &Attribute) -> String {
     route.to_string()
 }
 
+/// Extracts the name of the body parameter from a Rocket attribute like
+/// #[post("/path", data = "<body>")].
+///
+/// # Returns
+///
+/// The parameter name without angle brackets, or None if the route takes no body.
+pub fn handle_data_attr(attr: &Attribute) -> Option<String> {
+    let Ok(Meta::List(list)) = attr.parse_meta() else {
+        return None;
+    };
+
+    list.nested.iter().find_map(|nested| match nested {
+        NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("data") => {
+            match &name_value.lit {
+                Lit::Str(lit) => Some(
+                    lit.value()
+                        .trim_matches(|c| c == '<' || c == '>')
+                        .to_string(),
+                ),
+                _ => None,
+            }
+        }
+        _ => None,
+    })
+}
+
 impl<'ast> Visit<'ast> for Visitor {
+    fn visit_file(&mut self, file: &'ast File) {
+        self.cfg_stack.extend(cfg_predicates(&file.attrs));
+
+        visit::visit_file(self, file);
+    }
+
+    fn visit_item_mod(&mut self, item_mod: &'ast ItemMod) {
+        let depth = self.cfg_stack.len();
+        self.cfg_stack.extend(cfg_predicates(&item_mod.attrs));
+        self.module_path.push(item_mod.ident.to_string());
+
+        if item_mod.content.is_none() && !self.cfg_stack.is_empty() {
+            debug!("Found gated module {}", self.module_path.join("::"));
+            self.module_cfgs.push(ModuleCfg {
+                module_path: self.module_path.clone(),
+                cfg: self.cfg_stack.clone(),
+            });
+        }
+
+        visit::visit_item_mod(self, item_mod);
+
+        self.module_path.pop();
+        self.cfg_stack.truncate(depth);
+    }
+
     fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
-        for attr in &item_fn.attrs {
-            if let Some(first_segment) = attr.path.segments.first() {
-                if first_segment.ident == "get" || first_segment.ident == "post" {
-                    let function_name = item_fn.sig.ident.to_string();
+        self.visit_handler(&item_fn.attrs, &item_fn.sig);
 
-                    let path = handle_get_attr(attr);
+        visit::visit_item_fn(self, item_fn);
+    }
 
-                    let mut params: Vec<FnArg> = vec![];
-                    for input in &item_fn.sig.inputs {
-                        params.push((*input).clone());
-                    }
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        let depth = self.cfg_stack.len();
+        self.cfg_stack.extend(cfg_predicates(&item_impl.attrs));
+        let outer = self.impl_type.take();
+        if let Type::Path(type_path) = &*item_impl.self_ty {
+            self.impl_type = type_path.path.segments.last().map(|s| s.ident.to_string());
+        }
+        match trait_impl(item_impl) {
+            Some((trait_name, self_name)) if trait_name == "FromRequest" => {
+                debug!("Found request guard {self_name}");
+                self.guards.push(self_name);
+            }
+            Some((trait_name, self_name))
+                if matches!(
+                    trait_name.as_str(),
+                    "FromParam" | "FromSegments" | "FromFormField"
+                ) =>
+            {
+                debug!("Found URL value type {self_name}, implementing {trait_name}");
+                self.url_types.push(self_name);
+            }
+            _ => {}
+        }
 
-                    let return_type = item_fn.sig.output.clone();
+        visit::visit_item_impl(self, item_impl);
 
-                    let req_handler = RocketReqHandler {
-                        name: function_name,
-                        path,
-                        params,
-                        return_type,
-                    };
-                    self.functions.push(req_handler);
-                }
+        self.impl_type = outer;
+        self.cfg_stack.truncate(depth);
+    }
+
+    fn visit_impl_item_method(&mut self, method: &'ast ImplItemMethod) {
+        self.visit_handler(&method.attrs, &method.sig);
+
+        visit::visit_impl_item_method(self, method);
+    }
+
+    fn visit_item_struct(&mut self, item_struct: &'ast ItemStruct) {
+        if derives_url_value(&item_struct.attrs) {
+            debug!("Found URL value type {}, by derive", item_struct.ident);
+            self.url_types.push(item_struct.ident.to_string());
+        }
+        if derives_serde(&item_struct.attrs) && !rocket_ts_attr(&item_struct.attrs).skip {
+            if let Some(mut model) = RustModel::from_struct(item_struct) {
+                model.cfg = self.item_cfg(&item_struct.attrs);
+                model.module_path = self.module_path.clone();
+                self.models.push(model);
             }
         }
 
-        visit::visit_item_fn(self, item_fn);
+        visit::visit_item_struct(self, item_struct);
+    }
+
+    fn visit_item_enum(&mut self, item_enum: &'ast ItemEnum) {
+        if derives_url_value(&item_enum.attrs) {
+            debug!("Found URL value type {}, by derive", item_enum.ident);
+            self.url_types.push(item_enum.ident.to_string());
+        }
+        if derives_serde(&item_enum.attrs) && !rocket_ts_attr(&item_enum.attrs).skip {
+            let mut model = RustModel::from_enum(item_enum);
+            model.cfg = self.item_cfg(&item_enum.attrs);
+            model.module_path = self.module_path.clone();
+            self.models.push(model);
+        }
+
+        visit::visit_item_enum(self, item_enum);
+    }
+
+    fn visit_item_type(&mut self, item_type: &'ast ItemType) {
+        let mut path = self.module_path.clone();
+        path.push(item_type.ident.to_string());
+
+        self.aliases.push(TypeAlias {
+            path,
+            generics: item_type
+                .generics
+                .type_params()
+                .map(|param| param.ident.to_string())
+                .collect(),
+            ty: (*item_type.ty).clone(),
+            module_path: self.module_path.clone(),
+            cfg: self.item_cfg(&item_type.attrs),
+        });
+
+        visit::visit_item_type(self, item_type);
+    }
+
+    fn visit_item_use(&mut self, item_use: &'ast ItemUse) {
+        let cfg = self.item_cfg(&item_use.attrs);
+        let mut imports = vec![];
+        use_imports(&item_use.tree, vec![], &mut imports);
+
+        for (name, target) in imports {
+            self.imports.push(UseImport {
+                scope: self.module_path.clone(),
+                name,
+                target,
+                cfg: cfg.clone(),
+            });
+        }
     }
 }
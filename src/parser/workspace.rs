@@ -0,0 +1,172 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Component, Path, PathBuf};
+
+use log::debug;
+use toml::{Table, Value};
+
+use crate::parser::source_tree::SourceTree;
+
+/// A crate found through a Cargo manifest, see `workspace_crates`.
+pub struct WorkspaceCrate {
+    /// The name the crate is referred to by in Rust paths, i.e. `domain_model` for the package
+    /// `domain-model`.
+    pub name: String,
+    /// The directory holding the crate's `Cargo.toml`.
+    pub dir: PathBuf,
+}
+
+/// Reads and parses a `Cargo.toml` file.
+fn read_manifest(tree: &SourceTree, path: &Path) -> std::io::Result<Table> {
+    tree.read_to_string(path)?
+        .parse()
+        .map_err(|err| Error::new(ErrorKind::InvalidData, format!("{}: {err}", path.display())))
+}
+
+/// Joins a relative path onto a directory, removing `.` and `..` components so every crate has a
+/// single path, i.e. `api` + `../shared` -> `shared`.
+fn join(dir: &Path, path: &str) -> PathBuf {
+    let mut joined = PathBuf::new();
+    for component in dir.join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(joined.components().next_back(), Some(Component::Normal(_))) =>
+            {
+                joined.pop();
+            }
+            component => joined.push(component),
+        }
+    }
+    if joined.as_os_str().is_empty() {
+        joined.push(".");
+    }
+    joined
+}
+
+/// Returns the strings of an array value, i.e. the `members` of a workspace.
+fn strings(value: Option<&Value>) -> Vec<&str> {
+    value
+        .and_then(Value::as_array)
+        .map(|values| values.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Expands a workspace member, where a trailing `*` matches every crate in a directory.
+///
+/// i.e. "crates/*" -> [crates/api, crates/domain]
+fn member_dirs(tree: &SourceTree, root: &Path, member: &str) -> Vec<PathBuf> {
+    let Some(parent) = member.strip_suffix('*') else {
+        return vec![join(root, member)];
+    };
+
+    let mut dirs: Vec<PathBuf> = tree
+        .entries(&join(root, parent))
+        .map(|entries| {
+            entries
+                .into_iter()
+                .filter(|dir| tree.is_file(&dir.join("Cargo.toml")))
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
+
+/// Finds the crates of a Cargo workspace from its local manifests, without invoking Cargo.
+///
+/// These are the workspace members, or the package itself if the manifest isn't a workspace,
+/// plus every crate they depend on through a `path` dependency, transitively.
+///
+/// # Arguments
+///
+/// * `tree` - Where the manifests are read from, i.e. the git revision of `diff --base`, so
+///   crates added or renamed since are found as they were.
+/// * `manifest_path` - The path of the `Cargo.toml` of the workspace or package.
+pub fn workspace_crates(
+    tree: &SourceTree,
+    manifest_path: &Path,
+) -> std::io::Result<Vec<WorkspaceCrate>> {
+    let root_dir = match manifest_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let root = read_manifest(tree, manifest_path)?;
+
+    let mut pending = vec![];
+    let mut workspace_deps = BTreeMap::new();
+    if root.contains_key("package") {
+        pending.push(root_dir.clone());
+    }
+    if let Some(workspace) = root.get("workspace").and_then(Value::as_table) {
+        let excluded: Vec<PathBuf> = strings(workspace.get("exclude"))
+            .iter()
+            .map(|dir| join(&root_dir, dir))
+            .collect();
+        for member in strings(workspace.get("members")) {
+            pending.extend(
+                member_dirs(tree, &root_dir, member)
+                    .into_iter()
+                    .filter(|dir| !excluded.contains(dir)),
+            );
+        }
+        if let Some(deps) = workspace.get("dependencies").and_then(Value::as_table) {
+            for (name, dep) in deps {
+                if let Some(path) = dep.get("path").and_then(Value::as_str) {
+                    workspace_deps.insert(name.clone(), join(&root_dir, path));
+                }
+            }
+        }
+    }
+
+    let mut crates = vec![];
+    let mut seen = BTreeSet::new();
+    let mut next = 0;
+    while let Some(dir) = pending.get(next).cloned() {
+        next += 1;
+        if !seen.insert(dir.canonicalize().unwrap_or_else(|_| dir.clone())) {
+            continue;
+        }
+
+        let manifest = read_manifest(tree, &dir.join("Cargo.toml"))?;
+        let Some(package) = manifest.get("package").and_then(Value::as_table) else {
+            continue;
+        };
+        let name = manifest
+            .get("lib")
+            .and_then(|lib| lib.get("name"))
+            .or_else(|| package.get("name"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .replace('-', "_");
+
+        if let Some(deps) = manifest.get("dependencies").and_then(Value::as_table) {
+            for (dep_name, dep) in deps {
+                if let Some(path) = dep.get("path").and_then(Value::as_str) {
+                    pending.push(join(&dir, path));
+                } else if dep.get("workspace").and_then(Value::as_bool) == Some(true) {
+                    pending.extend(workspace_deps.get(dep_name).cloned());
+                }
+            }
+        }
+
+        debug!("Found crate {name} in {}", dir.display());
+        crates.push(WorkspaceCrate { name, dir });
+    }
+
+    Ok(crates)
+}
+
+/// Collects the Rust source files below a directory, recursively.
+pub fn source_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            source_files(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}